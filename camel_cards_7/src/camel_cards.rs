@@ -0,0 +1,5 @@
+pub mod benchmark;
+pub mod card;
+pub mod error;
+pub mod hand;
+pub mod ruleset;
@@ -0,0 +1,49 @@
+use std::time::{Duration, Instant};
+
+use crate::camel_cards::hand::{Hand, get_total_winnings};
+use crate::camel_cards::ruleset::Ruleset;
+use crate::generator::Generator;
+
+pub struct RankingBenchmark {
+    pub hands: usize,
+    pub parse_time: Duration,
+    pub key_sort_time: Duration,
+    pub compare_sort_time: Duration,
+}
+
+// Ranks random hands sorting by key, and sorting comparing type and then card
+// by card as the hands used to be compared
+pub fn run_ranking_benchmark(hands: usize, ruleset: &Ruleset) -> Result<RankingBenchmark, String> {
+    let mut generator = Generator::new(0x2545_f491_4f6c_dd1d);
+
+    let lines: Vec<String> = (0 .. hands).map(|_| {
+        let cards: String = (0 .. ruleset.hand_size)
+            .map(|_| ruleset.labels[generator.next_below(ruleset.labels.len() as u64) as usize])
+            .collect();
+        format!("{cards} {}", generator.next_below(1000) + 1)
+    }).collect();
+
+    let start = Instant::now();
+    let mut parsed: Vec<Hand> = lines.iter()
+        .map(|line| Hand::parse(line, ruleset))
+        .collect::<Result<_, _>>()
+        .map_err(|err| err.to_string())?;
+    let parse_time = start.elapsed();
+
+    let mut compared = parsed.clone();
+
+    let start = Instant::now();
+    get_total_winnings(&mut parsed);
+    let key_sort_time = start.elapsed();
+
+    let start = Instant::now();
+    compared.sort_by(|a, b| a.compare_by_cards(b, ruleset));
+    let compare_sort_time = start.elapsed();
+
+    // Equal hands can be in any order, so only the cards are compared
+    if parsed.iter().zip(&compared).any(|(a, b)| a.cards != b.cards) {
+        return Err("Sorting by key and comparing hands ranked the hands differently".to_string());
+    }
+
+    Ok(RankingBenchmark { hands, parse_time, key_sort_time, compare_sort_time })
+}
@@ -0,0 +1,46 @@
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Card {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+// From the weakest to the strongest, the labels of the built-in rulesets
+pub const ALL_CARDS: [Card; 13] = [Card::Two, Card::Three, Card::Four, Card::Five, Card::Six, Card::Seven,
+    Card::Eight, Card::Nine, Card::Ten, Card::Jack, Card::Queen, Card::King, Card::Ace];
+
+impl Card {
+
+    pub const fn label(self) -> char {
+        match self {
+            Card::Two => '2',
+            Card::Three => '3',
+            Card::Four => '4',
+            Card::Five => '5',
+            Card::Six => '6',
+            Card::Seven => '7',
+            Card::Eight => '8',
+            Card::Nine => '9',
+            Card::Ten => 'T',
+            Card::Jack => 'J',
+            Card::Queen => 'Q',
+            Card::King => 'K',
+            Card::Ace => 'A',
+        }
+    }
+
+    // Position in ALL_CARDS, which is also the rank in the labels of the built-in rulesets
+    pub fn rank(self) -> u8 {
+        self as u8
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum HandError {
+    MissingBid,
+    TooManyFields,
+    WrongHandSize { expected: usize, found: usize },
+    InvalidCard(char),
+    InvalidBid(String),
+}
+
+impl fmt::Display for HandError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandError::MissingBid => write!(f, "expected '<hand> <bid>'"),
+            HandError::TooManyFields => write!(f, "expected only '<hand> <bid>'"),
+            HandError::WrongHandSize { expected, found } => write!(f, "a hand has {expected} cards, not {found}"),
+            HandError::InvalidCard(label) => write!(f, "'{label}' is not a card"),
+            HandError::InvalidBid(bid) => write!(f, "'{bid}' is not a valid bid"),
+        }
+    }
+}
+
+impl std::error::Error for HandError {}
+
+#[derive(Debug, PartialEq)]
+pub enum RulesetError {
+    EmptyHand,
+    NoLabels,
+    TooManyLabels(usize),
+    DuplicateLabel(char),
+    UnknownWildcard(char),
+    KeyTooLong { hand_size: usize, labels: usize },
+}
+
+impl fmt::Display for RulesetError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesetError::EmptyHand => write!(f, "a hand needs at least one card"),
+            RulesetError::NoLabels => write!(f, "there are no card labels"),
+            RulesetError::TooManyLabels(labels) => write!(f, "{labels} labels are too many, up to 255 are supported"),
+            RulesetError::DuplicateLabel(label) => write!(f, "label '{label}' appears more than once"),
+            RulesetError::UnknownWildcard(label) => write!(f, "wildcard '{label}' is not one of the labels"),
+            RulesetError::KeyTooLong { hand_size, labels } =>
+                write!(f, "hands of {hand_size} cards out of {labels} labels do not fit in a sort key"),
+        }
+    }
+}
+
+impl std::error::Error for RulesetError {}
@@ -0,0 +1,289 @@
+use std::cmp::Ordering;
+
+use crate::camel_cards::card::{ALL_CARDS, Card};
+use crate::camel_cards::error::HandError;
+use crate::camel_cards::ruleset::Ruleset;
+
+pub const HAND_SIZE: usize = 5;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HandCards {
+    // Hands of the rulesets playing 5 cards of the standard deck, as both parts do
+    Standard([Card; HAND_SIZE]),
+    // Rank of every card in the labels of any other ruleset, 0 being the weakest
+    Ranks(Vec<u8>),
+}
+
+impl HandCards {
+
+    pub fn new(ranks: Vec<u8>, ruleset: &Ruleset) -> Self {
+        match <[u8; HAND_SIZE]>::try_from(ranks.as_slice()) {
+            Ok(ranks) if ruleset.has_standard_deck() => HandCards::Standard(ranks.map(|rank| ALL_CARDS[rank as usize])),
+            _ => HandCards::Ranks(ranks),
+        }
+    }
+
+    pub fn ranks(&self) -> Vec<u8> {
+        match self {
+            HandCards::Standard(cards) => cards.iter().map(|card| card.rank()).collect(),
+            HandCards::Ranks(ranks) => ranks.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hand {
+    pub cards: HandCards,
+    pub bid: u32,
+    pub hand_type: HandType,
+    // Type and card strengths packed in one integer, comparing keys compares hands
+    pub key: u128,
+}
+
+impl Hand {
+
+    pub fn new(cards: Vec<u8>, bid: u32, ruleset: &Ruleset) -> Self {
+        let hand_type = Self::get_hand_type(&cards, ruleset);
+        let key = Self::get_sort_key(hand_type, &cards, ruleset);
+
+        Hand { cards: HandCards::new(cards, ruleset), bid, hand_type, key }
+    }
+
+    // Reads a "<hand> <bid>" line like "32T3K 765"
+    pub fn parse(line: &str, ruleset: &Ruleset) -> Result<Self, HandError> {
+        let mut fields = line.split_whitespace();
+        let (Some(cards_str), Some(bid_str)) = (fields.next(), fields.next()) else {
+            return Err(HandError::MissingBid);
+        };
+        if fields.next().is_some() {
+            return Err(HandError::TooManyFields);
+        }
+
+        let cards: Vec<u8> = cards_str.chars()
+            .map(|label| ruleset.get_rank(label).ok_or(HandError::InvalidCard(label)))
+            .collect::<Result<_, _>>()?;
+        if cards.len() != ruleset.hand_size {
+            return Err(HandError::WrongHandSize { expected: ruleset.hand_size, found: cards.len() });
+        }
+
+        let bid = bid_str.parse::<u32>().map_err(|_| HandError::InvalidBid(bid_str.to_string()))?;
+
+        Ok(Hand::new(cards, bid, ruleset))
+    }
+
+    // Only the two biggest groups of equal cards matter, as in 5 card hands, and
+    // groups of more than five cards still count as five of a kind. Wildcards
+    // always do best joining the biggest group of the other cards.
+    pub fn get_hand_type(cards: &[u8], ruleset: &Ruleset) -> HandType {
+        let mut counts = vec![0usize; ruleset.labels.len()];
+        let mut number_wildcards: usize = 0;
+
+        for card in cards {
+            if ruleset.is_wildcard(*card) {
+                number_wildcards += 1;
+            }
+            else {
+                counts[*card as usize] += 1;
+            }
+        }
+
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        counts.push(0);
+        counts[0] += number_wildcards;
+
+        match (counts[0], counts[1]) {
+            (5 .., _) => HandType::FiveOfAKind,
+            (4, _) => HandType::FourOfAKind,
+            (3, 2 ..) => HandType::FullHouse,
+            (3, _) => HandType::ThreeOfAKind,
+            (2, 2) => HandType::TwoPair,
+            (2, _) => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+
+    // strength_bits per card, from the first card in the highest bits, and the type above them
+    pub fn get_sort_key(hand_type: HandType, cards: &[u8], ruleset: &Ruleset) -> u128 {
+        let bits = ruleset.strength_bits();
+
+        cards.iter().fold(hand_type as u128, |key, card| (key << bits) | ruleset.get_strength(*card) as u128)
+    }
+
+    // Same order as the keys, comparing the type and then card by card
+    pub fn compare_by_cards(&self, other: &Hand, ruleset: &Ruleset) -> Ordering {
+        let strengths = |hand: &Hand| hand.cards.ranks().into_iter().map(|card| ruleset.get_strength(card)).collect::<Vec<u32>>();
+
+        self.hand_type.cmp(&other.hand_type).then_with(|| strengths(self).cmp(&strengths(other)))
+    }
+}
+
+// Every hand wins its bid times its rank, the weakest hand having rank 1
+pub fn get_total_winnings(hands: &mut [Hand]) -> u64 {
+    hands.sort_unstable_by_key(|hand| hand.key);
+
+    hands.iter().enumerate().map(|(i, hand)| (i as u64 + 1) * hand.bid as u64).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::io::{BufRead, BufReader};
+
+    use super::*;
+    use crate::generator::Generator;
+
+    const EXAMPLE: &str = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+
+    // Type read straight from the sizes of the groups of equal labels
+    fn get_hand_type_reference(cards: &[u8]) -> HandType {
+        let mut frequency_cards_map: HashMap<u8, usize> = HashMap::new();
+        for card in cards {
+            *frequency_cards_map.entry(*card).or_insert(0) += 1;
+        }
+
+        let mut groups: Vec<usize> = frequency_cards_map.into_values().collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        groups.push(0);
+
+        match (groups[0], groups[1]) {
+            (biggest, _) if biggest >= 5 => HandType::FiveOfAKind,
+            (4, _) => HandType::FourOfAKind,
+            (3, second) if second >= 2 => HandType::FullHouse,
+            (3, _) => HandType::ThreeOfAKind,
+            (2, 2) => HandType::TwoPair,
+            (2, _) => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+
+    // The best type among every way of turning all the wildcards into the same label
+    fn get_wildcard_hand_type_reference(cards: &[u8], ruleset: &Ruleset) -> HandType {
+        if !cards.iter().any(|card| ruleset.is_wildcard(*card)) {
+            return get_hand_type_reference(cards);
+        }
+
+        (0 .. ruleset.labels.len() as u8).map(|replacement| {
+            let replaced: Vec<u8> = cards.iter()
+                .map(|card| if ruleset.is_wildcard(*card) {replacement} else {*card})
+                .collect();
+            get_hand_type_reference(&replaced)
+        }).max().unwrap_or(HandType::HighCard)
+    }
+
+    // Hands of up to this many cards are all checked, longer ones only through random pairs
+    const MAX_ENUMERATED_HAND_SIZE: usize = 3;
+
+    // Every possible short hand of the ruleset has to get the same type as the reference,
+    // and so do random pairs of hands, whose keys have to order them as comparing type
+    // and then card by card
+    fn check_ruleset(ruleset: &Ruleset, pairs: usize) {
+        let labels = ruleset.labels.len();
+
+        let all_hands = if ruleset.hand_size <= MAX_ENUMERATED_HAND_SIZE {(labels as u64).pow(ruleset.hand_size as u32)} else {0};
+        for index in 0 .. all_hands {
+            let mut rest = index;
+            let cards: Vec<u8> = (0 .. ruleset.hand_size).map(|_| {
+                let card = (rest % labels as u64) as u8;
+                rest /= labels as u64;
+                card
+            }).collect();
+
+            assert_eq!(Hand::get_hand_type(&cards, ruleset), get_wildcard_hand_type_reference(&cards, ruleset),
+                "hand {cards:?} of {ruleset:?}");
+        }
+
+        let mut generator = Generator::new(0x9e37_79b9_7f4a_7c15);
+        let mut random_hand = || {
+            let cards: Vec<u8> = (0 .. ruleset.hand_size).map(|_| generator.next_below(labels as u64) as u8).collect();
+            Hand::new(cards, 1, ruleset)
+        };
+
+        for _ in 0 .. pairs {
+            let (a, b) = (random_hand(), random_hand());
+
+            assert_eq!(a.key.cmp(&b.key), a.compare_by_cards(&b, ruleset), "hands {:?} and {:?} of {ruleset:?}", a.cards, b.cards);
+            assert_eq!(a.hand_type, get_wildcard_hand_type_reference(&a.cards.ranks(), ruleset), "hand {:?} of {ruleset:?}", a.cards);
+        }
+    }
+
+    fn get_winnings(lines: impl Iterator<Item = String>, ruleset: &Ruleset) -> u64 {
+        let mut hands: Vec<Hand> = lines.map(|line| Hand::parse(&line, ruleset).expect("valid hand")).collect();
+
+        get_total_winnings(&mut hands)
+    }
+
+    #[test]
+    fn built_in_rulesets_match_the_reference() {
+        check_ruleset(&Ruleset::standard(), 5000);
+        check_ruleset(&Ruleset::jokers(), 5000);
+    }
+
+    // Short and long hands, short hands of the standard deck with jokers, several wildcards
+    // keeping their rank, and the longest hands whose key still fits
+    #[test]
+    fn other_rulesets_match_the_reference() {
+        let rulesets = [
+            Ruleset::new(3, Ruleset::jokers().labels, vec!['J'], false),
+            Ruleset::new(3, "abcd".chars().collect(), vec!['a', 'c'], true),
+            Ruleset::new(7, "0123".chars().collect(), vec!['3'], false),
+            Ruleset::new(1, "xy".chars().collect(), vec!['y'], false),
+            Ruleset::new(31, "23456789TJQKA".chars().collect(), vec!['J', 'Q'], false),
+        ];
+
+        for ruleset in rulesets {
+            check_ruleset(&ruleset.expect("valid ruleset"), 5000);
+        }
+    }
+
+    #[test]
+    fn example_winnings() {
+        let lines = || EXAMPLE.lines().map(String::from);
+
+        assert_eq!(get_winnings(lines(), &Ruleset::standard()), 6440);
+        assert_eq!(get_winnings(lines(), &Ruleset::jokers()), 5905);
+    }
+
+    #[test]
+    fn input_winnings() {
+        let lines = || {
+            let file = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/data/input.txt")).expect("input file");
+            BufReader::new(file).lines().map(|line| line.expect("readable line"))
+        };
+
+        assert_eq!(get_winnings(lines(), &Ruleset::standard()), 252656917);
+        assert_eq!(get_winnings(lines(), &Ruleset::jokers()), 253499763);
+    }
+
+    #[test]
+    fn standard_deck_hands_keep_card_values() {
+        let hand = Hand::parse("32T3K 765", &Ruleset::jokers()).unwrap();
+        assert_eq!(hand.cards, HandCards::Standard([Card::Three, Card::Two, Card::Ten, Card::Three, Card::King]));
+        assert_eq!(hand.cards.ranks(), vec![1, 0, 8, 1, 11]);
+
+        let ruleset = Ruleset::new(3, "abcd".chars().collect(), vec!['a'], true).unwrap();
+        assert_eq!(Hand::parse("dab 1", &ruleset).unwrap().cards, HandCards::Ranks(vec![3, 0, 1]));
+    }
+
+    #[test]
+    fn malformed_lines() {
+        let ruleset = Ruleset::standard();
+
+        assert_eq!(Hand::parse("32T3K", &ruleset), Err(HandError::MissingBid));
+        assert_eq!(Hand::parse("32T3K 765 1", &ruleset), Err(HandError::TooManyFields));
+        assert_eq!(Hand::parse("32T3X 765", &ruleset), Err(HandError::InvalidCard('X')));
+        assert_eq!(Hand::parse("32T3 765", &ruleset), Err(HandError::WrongHandSize { expected: 5, found: 4 }));
+        assert_eq!(Hand::parse("32T3K -1", &ruleset), Err(HandError::InvalidBid("-1".to_string())));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::camel_cards::card::{ALL_CARDS, Card};
+use crate::camel_cards::error::RulesetError;
+use crate::camel_cards::hand::HAND_SIZE;
+
+// Types take 3 bits of the sort key, the rest holds the strength of every card
+const KEY_BITS: u32 = 128;
+const TYPE_BITS: u32 = 3;
+
+// The joker of Part Two
+const JOKER: Card = Card::Jack;
+
+#[derive(Clone, Debug)]
+pub struct Ruleset {
+    pub hand_size: usize,
+    // Card labels from the weakest to the strongest
+    pub labels: Vec<char>,
+    // Labels that join whichever group of cards makes the best hand
+    pub wildcards: Vec<char>,
+    // For tie-breaks a wildcard keeps the strength of its label, otherwise it is the weakest card
+    pub wildcard_own_rank: bool,
+}
+
+impl Ruleset {
+
+    pub fn new(hand_size: usize, labels: Vec<char>, wildcards: Vec<char>, wildcard_own_rank: bool) -> Result<Self, RulesetError> {
+        if hand_size == 0 {
+            return Err(RulesetError::EmptyHand);
+        }
+        if labels.is_empty() {
+            return Err(RulesetError::NoLabels);
+        }
+        if labels.len() > u8::MAX as usize {
+            return Err(RulesetError::TooManyLabels(labels.len()));
+        }
+        if let Some((_, label)) = labels.iter().enumerate().find(|(i, label)| labels[.. *i].contains(label)) {
+            return Err(RulesetError::DuplicateLabel(*label));
+        }
+        if let Some(wildcard) = wildcards.iter().find(|wildcard| !labels.contains(wildcard)) {
+            return Err(RulesetError::UnknownWildcard(*wildcard));
+        }
+
+        let ruleset = Ruleset { hand_size, labels, wildcards, wildcard_own_rank };
+        let key_bits = u32::try_from(hand_size).ok()
+            .and_then(|hand_size| hand_size.checked_mul(ruleset.strength_bits()))
+            .and_then(|cards_bits| cards_bits.checked_add(TYPE_BITS));
+        if key_bits.is_none_or(|key_bits| key_bits > KEY_BITS) {
+            return Err(RulesetError::KeyTooLong { hand_size, labels: ruleset.labels.len() });
+        }
+
+        Ok(ruleset)
+    }
+
+    // Part One: 5 cards from 2 up to A and no wildcards
+    pub fn standard() -> Self {
+        Ruleset {
+            hand_size: HAND_SIZE,
+            labels: ALL_CARDS.iter().map(|card| card.label()).collect(),
+            wildcards: Vec::new(),
+            wildcard_own_rank: true,
+        }
+    }
+
+    // Part Two: the J are jokers, the weakest card for tie-breaks
+    pub fn jokers() -> Self {
+        Ruleset {
+            wildcards: vec![JOKER.label()],
+            wildcard_own_rank: false,
+            ..Ruleset::standard()
+        }
+    }
+
+    // Position of the label from the weakest
+    pub fn get_rank(&self, label: char) -> Option<u8> {
+        self.labels.iter().position(|other| *other == label).map(|rank| rank as u8)
+    }
+
+    pub fn is_wildcard(&self, rank: u8) -> bool {
+        self.wildcards.contains(&self.labels[rank as usize])
+    }
+
+    // Weakest wildcards get 0 and the labels 1 up to their number
+    pub fn get_strength(&self, rank: u8) -> u32 {
+        if !self.wildcard_own_rank && self.is_wildcard(rank) {
+            return 0;
+        }
+
+        rank as u32 + 1
+    }
+
+    // 5 cards out of the labels of Part One, the hands keep them as Card values
+    pub fn has_standard_deck(&self) -> bool {
+        self.hand_size == HAND_SIZE && self.labels.iter().copied().eq(ALL_CARDS.iter().map(|card| card.label()))
+    }
+
+    pub fn strength_bits(&self) -> u32 {
+        u32::BITS - (self.labels.len() as u32).leading_zeros()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn broken_rulesets() {
+        let broken = [
+            (Ruleset::new(0, "ab".chars().collect(), Vec::new(), true), "a hand needs at least one card"),
+            (Ruleset::new(5, Vec::new(), Vec::new(), true), "there are no card labels"),
+            (Ruleset::new(5, "aba".chars().collect(), Vec::new(), true), "label 'a' appears more than once"),
+            (Ruleset::new(5, "ab".chars().collect(), vec!['c'], true), "wildcard 'c' is not one of the labels"),
+            (Ruleset::new(32, Ruleset::standard().labels, Vec::new(), true),
+                "hands of 32 cards out of 13 labels do not fit in a sort key"),
+            (Ruleset::new(1 << 31, "ab".chars().collect(), Vec::new(), true),
+                "hands of 2147483648 cards out of 2 labels do not fit in a sort key"),
+            (Ruleset::new(usize::MAX, "ab".chars().collect(), Vec::new(), true),
+                &format!("hands of {} cards out of 2 labels do not fit in a sort key", usize::MAX)),
+        ];
+
+        for (result, expected) in broken {
+            assert_eq!(result.expect_err(expected).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn standard_deck() {
+        assert!(Ruleset::standard().has_standard_deck());
+        assert!(Ruleset::jokers().has_standard_deck());
+        assert!(!Ruleset { hand_size: 6, ..Ruleset::standard() }.has_standard_deck());
+        assert!(!Ruleset { labels: "23456789TQJKA".chars().collect(), ..Ruleset::standard() }.has_standard_deck());
+    }
+}
@@ -1 +1,2 @@
-pub mod min_heap;
\ No newline at end of file
+pub mod indexed_heap;
+pub mod min_heap;
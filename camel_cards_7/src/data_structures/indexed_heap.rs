@@ -0,0 +1,368 @@
+use std::cmp::Ordering;
+
+use crate::data_structures::min_heap::{Compare, MinOrder};
+
+// Refers to one element pushed into an IndexedHeap. Once the element is popped or
+// removed the handle stays invalid, even if its slot is reused by a later push.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: usize,
+    generation: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<P, T> {
+    priority: P,
+    value: T,
+    generation: u64,
+    // Index of the slot in the heap vector
+    position: usize,
+}
+
+// Heap of (priority, value) pairs that can change the priority of any element or
+// remove it through the handle given by push, as Dijkstra's decrease-key needs
+#[derive(Debug, Clone)]
+pub struct IndexedHeap<P, T, C = MinOrder> {
+    heap: Vec<usize>,
+    slots: Vec<Option<Entry<P, T>>>,
+    free_slots: Vec<usize>,
+    next_generation: u64,
+    order: C,
+}
+
+impl<P: Ord, T> IndexedHeap<P, T, MinOrder> {
+    pub fn new() -> Self {
+        IndexedHeap::with_comparator(MinOrder)
+    }
+}
+
+impl<P: Ord, T> Default for IndexedHeap<P, T, MinOrder> {
+    fn default() -> Self {
+        IndexedHeap::new()
+    }
+}
+
+impl<P, T, C: Compare<P>> IndexedHeap<P, T, C> {
+    pub fn with_comparator(order: C) -> Self {
+        IndexedHeap { heap: Vec::new(), slots: Vec::new(), free_slots: Vec::new(), next_generation: 0, order }
+    }
+
+    pub fn push(&mut self, priority: P, value: T) -> Handle {
+        let generation = self.next_generation;
+        self.next_generation += 1;
+
+        let entry = Entry { priority, value, generation, position: self.heap.len() };
+        let slot = match self.free_slots.pop() {
+            Some(slot) => {
+                self.slots[slot] = Some(entry);
+                slot
+            },
+            None => {
+                self.slots.push(Some(entry));
+                self.slots.len() - 1
+            },
+        };
+
+        self.heap.push(slot);
+        self.heapify_up(self.heap.len() - 1);
+
+        Handle { slot, generation }
+    }
+
+    pub fn pop(&mut self) -> Option<(P, T)> {
+        let slot = *self.heap.first()?;
+        self.remove_slot(slot)
+    }
+
+    pub fn peek(&self) -> Option<(&P, &T)> {
+        let entry = self.entry(*self.heap.first()?);
+        Some((&entry.priority, &entry.value))
+    }
+
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.slots.get(handle.slot)
+            .is_some_and(|entry| entry.as_ref().is_some_and(|entry| entry.generation == handle.generation))
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<(&P, &T)> {
+        if !self.contains(handle) {
+            return None;
+        }
+        let entry = self.entry(handle.slot);
+        Some((&entry.priority, &entry.value))
+    }
+
+    // Returns the previous priority, None if the handle is no longer in the heap
+    pub fn change_priority(&mut self, handle: Handle, priority: P) -> Option<P> {
+        if !self.contains(handle) {
+            return None;
+        }
+
+        let entry = self.slots[handle.slot].as_mut()?;
+        let position = entry.position;
+        let old_priority = std::mem::replace(&mut entry.priority, priority);
+
+        // Only one of them moves the element
+        self.heapify_up(position);
+        let position = self.entry(handle.slot).position;
+        self.heapify_down(position);
+
+        Some(old_priority)
+    }
+
+    pub fn remove(&mut self, handle: Handle) -> Option<(P, T)> {
+        if !self.contains(handle) {
+            return None;
+        }
+        self.remove_slot(handle.slot)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    fn remove_slot(&mut self, slot: usize) -> Option<(P, T)> {
+        let entry = self.slots[slot].take()?;
+        self.free_slots.push(slot);
+
+        let position = entry.position;
+        self.heap.swap_remove(position);
+
+        // The last element took the place of the removed one, it may have to go either way
+        if position < self.heap.len() {
+            let moved_slot = self.heap[position];
+            self.entry_mut(moved_slot).position = position;
+            self.heapify_up(position);
+            let position = self.entry(moved_slot).position;
+            self.heapify_down(position);
+        }
+
+        Some((entry.priority, entry.value))
+    }
+
+    fn entry(&self, slot: usize) -> &Entry<P, T> {
+        self.slots[slot].as_ref().expect("every slot in the heap holds an entry")
+    }
+
+    fn entry_mut(&mut self, slot: usize) -> &mut Entry<P, T> {
+        self.slots[slot].as_mut().expect("every slot in the heap holds an entry")
+    }
+
+    fn compare_positions(&self, a: usize, b: usize) -> Ordering {
+        self.order.compare(&self.entry(self.heap[a]).priority, &self.entry(self.heap[b]).priority)
+    }
+
+    fn swap_positions(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        let (slot_a, slot_b) = (self.heap[a], self.heap[b]);
+        self.entry_mut(slot_a).position = a;
+        self.entry_mut(slot_b).position = b;
+    }
+
+    fn heapify_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.compare_positions(parent, index) != Ordering::Greater {
+                break;
+            }
+            self.swap_positions(parent, index);
+            index = parent;
+        }
+    }
+
+    fn heapify_down(&mut self, mut index: usize) {
+        let len = self.heap.len();
+        loop {
+            let child = 2 * index + 1;
+            if child >= len {
+                break;
+            }
+            let mut min_child = child;
+            if child + 1 < len && self.compare_positions(child, child + 1) == Ordering::Greater {
+                min_child = child + 1;
+            }
+            if self.compare_positions(min_child, index) != Ordering::Less {
+                break;
+            }
+            self.swap_positions(index, min_child);
+            index = min_child;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::data_structures::min_heap::{MaxOrder, MinHeap};
+    use crate::generator::Generator;
+
+    // Random pushes, pops, priority changes and removals against a plain list of the
+    // live elements: the heap has to pop an element of the lowest priority, and
+    // handles of popped or removed elements have to stay invalid
+    #[test]
+    fn operations_match_a_plain_list() {
+        let mut generator = Generator::new(0x853c_49e6_748f_ea9b);
+
+        let mut heap: IndexedHeap<u32, u64> = IndexedHeap::new();
+        let mut live: Vec<(Handle, u32, u64)> = Vec::new();
+        let mut dead: Vec<Handle> = Vec::new();
+        let mut given: HashSet<Handle> = HashSet::new();
+
+        for i in 0 .. 100_000 {
+            match generator.next_below(10) {
+                0 ..= 3 => {
+                    let priority = generator.next_below(1000) as u32;
+                    let handle = heap.push(priority, i);
+                    assert!(given.insert(handle), "operation {i}: push gave the handle {handle:?} twice");
+                    live.push((handle, priority, i));
+                },
+                4 | 5 => {
+                    let min_priority = live.iter().map(|(_, priority, _)| *priority).min();
+
+                    match heap.pop() {
+                        Some((priority, value)) => {
+                            assert_eq!(Some(priority), min_priority, "operation {i}");
+                            let index = live.iter().position(|(_, _, other_value)| *other_value == value)
+                                .expect("popped an element which was not in the heap");
+                            assert_eq!(live[index].1, priority, "operation {i}");
+                            dead.push(live.swap_remove(index).0);
+                        },
+                        None => assert!(live.is_empty(), "operation {i}"),
+                    }
+                },
+                6 | 7 => {
+                    let priority = generator.next_below(1000) as u32;
+                    if !live.is_empty() {
+                        let index = generator.next_below(live.len() as u64) as usize;
+                        assert_eq!(heap.change_priority(live[index].0, priority), Some(live[index].1), "operation {i}");
+                        live[index].1 = priority;
+                    }
+                    if let Some(handle) = dead.last() {
+                        assert_eq!(heap.change_priority(*handle, priority), None, "operation {i}");
+                    }
+                },
+                _ => {
+                    if !live.is_empty() {
+                        let index = generator.next_below(live.len() as u64) as usize;
+                        let (handle, priority, value) = live.swap_remove(index);
+                        assert_eq!(heap.remove(handle), Some((priority, value)), "operation {i}");
+                        assert_eq!(heap.remove(handle), None, "operation {i}");
+                        dead.push(handle);
+                    }
+                },
+            }
+
+            let min_priority = live.iter().map(|(_, priority, _)| *priority).min();
+            assert_eq!(heap.len(), live.len(), "operation {i}");
+            assert_eq!(heap.peek().map(|(priority, _)| *priority), min_priority, "operation {i}");
+
+            if let Some((handle, priority, value)) = live.get(i as usize % live.len().max(1)) {
+                assert_eq!(heap.get(*handle), Some((priority, value)), "operation {i}");
+            }
+            assert!(dead.iter().rev().take(3).all(|handle| !heap.contains(*handle) && heap.get(*handle).is_none()),
+                "operation {i}: a removed handle is still in the heap");
+        }
+    }
+
+    #[test]
+    fn reused_slots_do_not_revive_handles() {
+        let mut heap: IndexedHeap<u32, char> = IndexedHeap::new();
+
+        let first = heap.push(5, 'a');
+        assert_eq!(heap.pop(), Some((5, 'a')));
+
+        let second = heap.push(7, 'b');
+        assert_ne!(first, second);
+        assert!(!heap.contains(first) && heap.contains(second));
+        assert_eq!(heap.change_priority(first, 1), None);
+        assert_eq!(heap.remove(first), None);
+        assert_eq!(heap.peek(), Some((&7, &'b')));
+    }
+
+    #[test]
+    fn other_orders() {
+        let mut heap: IndexedHeap<u32, char, MaxOrder> = IndexedHeap::with_comparator(MaxOrder);
+        let handles: Vec<Handle> = [(3, 'a'), (9, 'b'), (5, 'c')].iter().map(|(priority, value)| heap.push(*priority, *value)).collect();
+
+        assert_eq!(heap.change_priority(handles[0], 10), Some(3));
+        assert_eq!(heap.remove(handles[2]), Some((5, 'c')));
+        assert_eq!((heap.pop(), heap.pop(), heap.pop()), (Some((10, 'a')), Some((9, 'b')), None));
+    }
+
+    // Distances from node 0 with decrease-key on an IndexedHeap
+    fn dijkstra_indexed(edges: &[Vec<(usize, u64)>]) -> Vec<Option<u64>> {
+        let mut distances: Vec<Option<u64>> = vec![None; edges.len()];
+        let mut handles: Vec<Option<Handle>> = vec![None; edges.len()];
+        let mut done: Vec<bool> = vec![false; edges.len()];
+        let mut heap: IndexedHeap<u64, usize> = IndexedHeap::new();
+
+        distances[0] = Some(0);
+        handles[0] = Some(heap.push(0, 0));
+
+        while let Some((distance, node)) = heap.pop() {
+            done[node] = true;
+
+            for (next, weight) in &edges[node] {
+                let next_distance = distance + weight;
+                if done[*next] || distances[*next].is_some_and(|current| current <= next_distance) {
+                    continue;
+                }
+
+                distances[*next] = Some(next_distance);
+                match handles[*next] {
+                    Some(handle) => { heap.change_priority(handle, next_distance); },
+                    None => handles[*next] = Some(heap.push(next_distance, *next)),
+                }
+            }
+        }
+
+        distances
+    }
+
+    // Same distances pushing a new entry for every improvement and skipping the stale ones
+    fn dijkstra_lazy(edges: &[Vec<(usize, u64)>]) -> Vec<Option<u64>> {
+        let mut distances: Vec<Option<u64>> = vec![None; edges.len()];
+        let mut heap: MinHeap<(u64, usize)> = MinHeap::new();
+
+        distances[0] = Some(0);
+        heap.push((0, 0));
+
+        while let Some((distance, node)) = heap.pop() {
+            if distances[node].is_some_and(|current| current < distance) {
+                continue;
+            }
+
+            for (next, weight) in &edges[node] {
+                let next_distance = distance + weight;
+                if distances[*next].is_none_or(|current| next_distance < current) {
+                    distances[*next] = Some(next_distance);
+                    heap.push((next_distance, *next));
+                }
+            }
+        }
+
+        distances
+    }
+
+    #[test]
+    fn dijkstra_with_decrease_key_matches_lazy_deletion() {
+        let mut generator = Generator::new(0xda94_2042_e4dd_58b5);
+        let nodes = 200;
+
+        for graph in 0 .. 500 {
+            let edges: Vec<Vec<(usize, u64)>> = (0 .. nodes).map(|_| {
+                (0 .. generator.next_below(6))
+                    .map(|_| (generator.next_below(nodes as u64) as usize, generator.next_below(100)))
+                    .collect()
+            }).collect();
+
+            assert_eq!(dijkstra_indexed(&edges), dijkstra_lazy(&edges), "graph {graph}");
+        }
+    }
+}
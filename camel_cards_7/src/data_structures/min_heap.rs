@@ -1,15 +1,92 @@
-use std::cmp::Ord;
-use std::fmt::Debug;
-use std::clone::Clone;
+use std::cmp::{Ord, Ordering};
 
-#[derive(Debug)]
-pub struct MinHeap<T: Ord + Clone + Debug> {
+// Order of the elements in a heap, the element comparing Less is popped first
+pub trait Compare<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MinOrder;
+
+impl<T: Ord> Compare<T> for MinOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MaxOrder;
+
+impl<T: Ord> Compare<T> for MaxOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+// Smallest key first
+#[derive(Clone, Copy)]
+pub struct KeyOrder<F>(pub F);
+
+impl<T, K: Ord, F: Fn(&T) -> K> Compare<T> for KeyOrder<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a).cmp(&(self.0)(b))
+    }
+}
+
+// Any closure comparing two elements
+impl<T, F: Fn(&T, &T) -> Ordering> Compare<T> for F {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Heap<T, C> {
     data: Vec<T>,
+    order: C,
 }
 
-impl<T: Ord + Clone + Debug> MinHeap<T> {
+pub type MinHeap<T> = Heap<T, MinOrder>;
+pub type MaxHeap<T> = Heap<T, MaxOrder>;
+
+impl<T: Ord> MinHeap<T> {
     pub fn new() -> Self {
-        MinHeap { data: Vec::new() }
+        Heap::with_comparator(MinOrder)
+    }
+
+    pub fn from_vec(data: Vec<T>) -> Self {
+        Heap::from_vec_with_comparator(data, MinOrder)
+    }
+}
+
+impl<T: Ord> MaxHeap<T> {
+    pub fn new() -> Self {
+        Heap::with_comparator(MaxOrder)
+    }
+
+    pub fn from_vec(data: Vec<T>) -> Self {
+        Heap::from_vec_with_comparator(data, MaxOrder)
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> Heap<T, KeyOrder<F>> {
+    pub fn with_key(key: F) -> Self {
+        Heap::with_comparator(KeyOrder(key))
+    }
+}
+
+impl<T, C: Compare<T>> Heap<T, C> {
+    pub fn with_comparator(order: C) -> Self {
+        Heap { data: Vec::new(), order }
+    }
+
+    // Sifting down every parent from the last one is O(n), against O(n log n) pushing one by one
+    pub fn from_vec_with_comparator(data: Vec<T>, order: C) -> Self {
+        let mut heap = Heap { data, order };
+        for index in (0 .. heap.data.len() / 2).rev() {
+            heap.heapify_down(index);
+        }
+        heap
     }
 
     pub fn push(&mut self, value: T) {
@@ -29,18 +106,46 @@ impl<T: Ord + Clone + Debug> MinHeap<T> {
         }
     }
 
-    pub fn empty(&self) -> bool{
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
         self.data.is_empty()
     }
 
-    pub fn top(&self) -> Option<T> {
-        self.data.get(0).cloned()
+    // Elements in the order pop would give them
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted: Vec<T> = Vec::with_capacity(self.data.len());
+        while let Some(value) = self.pop() {
+            sorted.push(value);
+        }
+        sorted
+    }
+
+    // Elements in heap order, not sorted
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    // Removes every element, in heap order, not sorted
+    pub fn drain(&mut self) -> std::vec::Drain<'_, T> {
+        self.data.drain(..)
+    }
+
+    // Elements in heap order, not sorted
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
     }
 
     fn heapify_up(&mut self, mut index: usize) {
         while index > 0 {
             let parent = (index - 1) / 2;
-            if self.data[parent] <= self.data[index] {
+            if self.order.compare(&self.data[parent], &self.data[index]) != Ordering::Greater {
                 break;
             }
             self.data.swap(parent, index);
@@ -56,10 +161,10 @@ impl<T: Ord + Clone + Debug> MinHeap<T> {
                 break;
             }
             let mut min_child = child;
-            if child + 1 < len && self.data[child] > self.data[child + 1] {
+            if child + 1 < len && self.order.compare(&self.data[child], &self.data[child + 1]) == Ordering::Greater {
                 min_child = child + 1;
             }
-            if self.data[min_child] >= self.data[index] {
+            if self.order.compare(&self.data[min_child], &self.data[index]) != Ordering::Less {
                 break;
             }
             self.data.swap(index, min_child);
@@ -67,3 +172,135 @@ impl<T: Ord + Clone + Debug> MinHeap<T> {
         }
     }
 }
+
+impl<T, C: Compare<T> + Default> Default for Heap<T, C> {
+    fn default() -> Self {
+        Heap::with_comparator(C::default())
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for Heap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, C: Compare<T> + Default> FromIterator<T> for Heap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Heap::from_vec_with_comparator(iter.into_iter().collect(), C::default())
+    }
+}
+
+impl<'a, T, C: Compare<T>> IntoIterator for &'a Heap<T, C> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    use super::*;
+    use crate::generator::Generator;
+
+    // Runs the same random pushes and pops on the heaps and on BinaryHeap, comparing
+    // every popped element, peek and len. Values are small so there are many repeated ones.
+    #[test]
+    fn operations_match_binary_heap() {
+        let mut generator = Generator::new(0x9e37_79b9_7f4a_7c15);
+
+        let mut min_heap: MinHeap<u32> = MinHeap::new();
+        let mut max_heap: MaxHeap<u32> = MaxHeap::new();
+        let mut key_heap = Heap::with_key(|pair: &(u32, u32)| pair.1);
+        let mut closure_heap = Heap::with_comparator(|a: &i64, b: &i64| a.abs().cmp(&b.abs()).then(b.cmp(a)));
+
+        let mut std_min: BinaryHeap<Reverse<u32>> = BinaryHeap::new();
+        let mut std_max: BinaryHeap<u32> = BinaryHeap::new();
+        let mut std_key: BinaryHeap<Reverse<u32>> = BinaryHeap::new();
+        let mut std_closure: BinaryHeap<Reverse<(i64, Reverse<i64>)>> = BinaryHeap::new();
+
+        for i in 0 .. 200_000 {
+            // More pushes than pops, so the heaps grow and shrink back
+            if generator.next_below(5) < 3 {
+                let value = generator.next_below(100) as u32;
+                let signed = value as i64 - 50;
+
+                min_heap.push(value);
+                max_heap.push(value);
+                key_heap.push((i, value));
+                closure_heap.push(signed);
+
+                std_min.push(Reverse(value));
+                std_max.push(value);
+                std_key.push(Reverse(value));
+                std_closure.push(Reverse((signed.abs(), Reverse(signed))));
+            }
+            else {
+                assert_eq!(min_heap.pop(), std_min.pop().map(|value| value.0), "operation {i}");
+                assert_eq!(max_heap.pop(), std_max.pop(), "operation {i}");
+                assert_eq!(key_heap.pop().map(|pair| pair.1), std_key.pop().map(|value| value.0), "operation {i}");
+                assert_eq!(closure_heap.pop(), std_closure.pop().map(|value| (value.0).1.0), "operation {i}");
+            }
+
+            assert_eq!(min_heap.peek().copied(), std_min.peek().map(|value| value.0), "operation {i}");
+            assert_eq!(max_heap.peek().copied(), std_max.peek().copied(), "operation {i}");
+            assert_eq!(key_heap.peek().map(|pair| pair.1), std_key.peek().map(|value| value.0), "operation {i}");
+            assert_eq!((min_heap.len(), closure_heap.len()), (std_min.len(), std_closure.len()), "operation {i}");
+        }
+    }
+
+    // Building a heap at once, collecting, extending, iterating and draining have to
+    // keep every element, and the sorted vectors have to match a sort
+    #[test]
+    fn building_keeps_every_element() {
+        let mut generator = Generator::new(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0 .. 2_000 {
+            let values: Vec<u32> = (0 .. generator.next_below(200)).map(|_| generator.next_below(50) as u32).collect();
+            let mut sorted = values.clone();
+            sorted.sort_unstable();
+            let mut reversed = sorted.clone();
+            reversed.reverse();
+
+            assert_eq!(MinHeap::from_vec(values.clone()).into_sorted_vec(), sorted);
+            assert_eq!(MaxHeap::from_vec(values.clone()).into_sorted_vec(), reversed);
+            assert_eq!(BinaryHeap::from(values.clone()).into_sorted_vec(), sorted);
+
+            let collected: MinHeap<u32> = values.iter().copied().collect();
+            let mut iterated: Vec<u32> = collected.iter().copied().collect();
+            iterated.sort_unstable();
+            assert_eq!(iterated, sorted);
+            assert_eq!(collected.into_sorted_vec(), sorted);
+
+            let mut extended: MinHeap<u32> = MinHeap::from_vec(values[.. values.len() / 2].to_vec());
+            extended.extend(values[values.len() / 2 ..].iter().copied());
+            assert_eq!(extended.into_sorted_vec(), sorted);
+
+            let mut drained_heap: MinHeap<u32> = MinHeap::from_vec(values.clone());
+            let mut drained: Vec<u32> = drained_heap.drain().collect();
+            drained.sort_unstable();
+            assert_eq!(drained, sorted);
+            assert!(drained_heap.is_empty());
+
+            let by_key = Heap::from_vec_with_comparator(values.clone(), |a: &u32, b: &u32| (a % 7).cmp(&(b % 7)));
+            let keys: Vec<u32> = by_key.into_sorted_vec().iter().map(|value| value % 7).collect();
+            assert!(keys.is_sorted(), "a heap with a comparator popped keys out of order: {keys:?}");
+        }
+    }
+
+    #[test]
+    fn empty_heaps() {
+        let mut heap: MinHeap<u32> = MinHeap::default();
+
+        assert_eq!((heap.pop(), heap.peek(), heap.len()), (None, None, 0));
+        assert!(heap.into_sorted_vec().is_empty());
+        assert!(MaxHeap::<u32>::from_vec(Vec::new()).is_empty());
+    }
+}
@@ -73,11 +73,13 @@
 
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use std::collections::HashMap;
-use std::cmp::Ordering;
-use crate::data_structures::min_heap::MinHeap;
+use crate::camel_cards::{benchmark::run_ranking_benchmark,
+                         hand::{Hand, get_total_winnings},
+                         ruleset::Ruleset};
 
+pub mod camel_cards;
 pub mod data_structures;
+pub mod generator;
 
 #[derive(PartialEq, Debug)]
 enum TypeRun {
@@ -86,237 +88,86 @@ enum TypeRun {
 }
 
 fn main() -> std::io::Result<()>{
-    algorithm(TypeRun::FirstPart)?;
-    algorithm(TypeRun::SecondPart)?;
-
-    Ok(())
-}
-
-fn algorithm(type_run: TypeRun) -> std::io::Result<()> {
-    
-    let file:File = File::open("data/input.txt")?;
-    let reader: BufReader<File> = BufReader::new(file);
-    let mut hands_heap: MinHeap<HandInfo> = MinHeap::new();
-
-    let use_joker: bool = if type_run == TypeRun::FirstPart {false} else {true};
-
-    for line in reader.lines(){
-        let line_str:String = line?;
-        hands_heap.push(HandInfo::new(&line_str, use_joker));
-    }
-
-    println!("The result of {:?} is: {}", type_run, get_camel_card_result(&mut hands_heap));
-
-    Ok(())
-}
-
-fn get_camel_card_result(hands_data: &mut MinHeap<HandInfo>) -> u64{
-    let mut result: u64 = 0;
-    let mut multiplier: u64 = 1;
-
-    while !hands_data.empty() {
-
-        match hands_data.top() {
-            Some(x) => {
-                result += multiplier * x.value as u64;
-                multiplier += 1;
+    let args: Vec<String> = std::env::args().collect();
+
+    match args.get(1).map(|arg| arg.as_str()) {
+        // Rank generated hands sorting by key against comparing them card by card
+        Some("bench") => {
+            let hands = match args.get(2) {
+                Some(hands) => hands.parse::<usize>().map_err(std::io::Error::other)?,
+                None => 1_000_000,
+            };
+
+            let ruleset = read_ruleset(&args)?.unwrap_or_else(Ruleset::jokers);
+            let result = run_ranking_benchmark(hands, &ruleset).map_err(std::io::Error::other)?;
+            println!("Hands: {}", result.hands);
+            println!("Parsing: {:?}", result.parse_time);
+            println!("Sorting by key: {:?}", result.key_sort_time);
+            println!("Sorting comparing cards: {:?}", result.compare_sort_time);
+        },
+        // Any of --hand-size <n>, --labels <weakest to strongest>, --wildcards <labels>
+        // or --wildcard-own-rank plays other rules, starting from the Part One ones
+        _ => match read_ruleset(&args)? {
+            Some(ruleset) => {
+                let total_winnings = algorithm(&ruleset)?;
+                println!("The result of the custom rules is: {total_winnings}");
             },
-            None => println!("not data found on peek! :("),
-        }
-
-        hands_data.pop();
+            None => {
+                for type_run in [TypeRun::FirstPart, TypeRun::SecondPart] {
+                    let ruleset = match type_run {
+                        TypeRun::FirstPart => Ruleset::standard(),
+                        TypeRun::SecondPart => Ruleset::jokers(),
+                    };
+                    println!("The result of {:?} is: {}", type_run, algorithm(&ruleset)?);
+                }
+            },
+        },
     }
 
-
-    result
-}
-
-#[derive(PartialEq, Eq, PartialOrd, Clone, Debug)]
-enum HandType {
-    HighCard,
-    OnePair,
-    TwoPair,
-    ThreeOfAKind,
-    FullHouse,
-    FourOfAKind,
-    FiveOfAKind,
-}
-
-#[derive(PartialEq, Eq, Clone, Debug)]
-struct HandInfo {
-    data: String,
-    value: u16,
-    h_type: HandType,
-    using_joker: bool,
+    Ok(())
 }
 
-impl HandInfo {
+fn read_ruleset(args: &[String]) -> std::io::Result<Option<Ruleset>> {
 
-    fn new(info_card: &str, with_joker: bool) -> Self {
+    // Wildcards are the weakest cards unless told otherwise
+    let mut ruleset = Ruleset { wildcard_own_rank: false, ..Ruleset::standard() };
+    let mut any_flag = false;
 
-        let data:Vec<&str> = info_card.split_whitespace().collect();
+    for (i, arg) in args.iter().enumerate() {
+        let value = args.get(i + 1).map(|value| value.as_str());
 
-        HandInfo {
-            data: data[0].to_string(),
-            value: data[1].parse::<u16>().unwrap_or(0),
-            h_type: Self::get_type_hand(data[0], with_joker),
-            using_joker: with_joker
+        match (arg.as_str(), value) {
+            ("--wildcard-own-rank", _) => ruleset.wildcard_own_rank = true,
+            ("--hand-size", Some(value)) => ruleset.hand_size = value.parse::<usize>().map_err(std::io::Error::other)?,
+            ("--labels", Some(value)) => ruleset.labels = value.chars().collect(),
+            ("--wildcards", Some(value)) => ruleset.wildcards = value.chars().collect(),
+            ("--hand-size" | "--labels" | "--wildcards", None) =>
+                return Err(std::io::Error::other(format!("Expected a value after {arg} :("))),
+            _ => continue,
         }
+        any_flag = true;
     }
 
-    fn get_type_hand(data_card: &str, with_joker: bool) -> HandType{
-
-        let mut frequency_chars_map: HashMap<char, u8> = HashMap::new();
-        let mut number_jokers: u8 = 0;
-        for c in data_card.chars() {
-            if let Some(x) = frequency_chars_map.get_mut(&c) {
-                *x += 1;
-            }
-            else {
-                frequency_chars_map.insert(c, 1);
-            }
-
-            if c == 'J' {number_jokers +=1;}
-        }
-
-        // Convert to vector and sort
-        let mut frequency_chars_vec: Vec<(&char, &u8)> = frequency_chars_map.iter().collect();
-        frequency_chars_vec.sort_by(|a, b| b.1.cmp(&a.1));
-
-        let mut hand_type: HandType;
-        if *frequency_chars_vec[0].1 == 5 {
-            hand_type = HandType::FiveOfAKind;
-        }
-        else if *frequency_chars_vec[0].1 == 4 {
-            hand_type = HandType::FourOfAKind   
-        }
-        else if *frequency_chars_vec[0].1 == 3 {
-            
-            if (frequency_chars_vec.len() > 1) && (*frequency_chars_vec[1].1 == 2) {
-                hand_type = HandType::FullHouse;
-            }
-            else {
-                hand_type = HandType::ThreeOfAKind;
-            }   
-        }
-        else if *frequency_chars_vec[0].1 == 2 {
-            
-            if (frequency_chars_vec.len() > 1) && (*frequency_chars_vec[1].1 == 2) {
-                hand_type = HandType::TwoPair;
-            }
-            else {
-                hand_type = HandType::OnePair;
-            }   
-        }
-        else {
-            hand_type = HandType::HighCard;
-        }
-
-        // Modifying hand type in case there is a joker
-        if with_joker && (number_jokers > 0) {
-
-            if hand_type >= HandType::FullHouse {
-                hand_type = HandType::FiveOfAKind;
-            }
-            else if hand_type == HandType::ThreeOfAKind {
-                if number_jokers < 3 {
-                    hand_type = HandType::FourOfAKind;
-                }
-            }
-            else if hand_type == HandType::TwoPair {
-                if number_jokers == 1 {
-                    hand_type = HandType::FullHouse;
-                }
-                else {
-                    hand_type = HandType::FourOfAKind;
-                }
-            }
-            else if  hand_type == HandType::OnePair {
-                hand_type = HandType::ThreeOfAKind;
-            }
-            else {
-                hand_type = HandType::OnePair;
-            }
-        }
-
-        hand_type
+    if !any_flag {
+        return Ok(None);
     }
 
-    fn get_relative_streght(card: char, with_joker: bool) -> u8{
-
-        let streght: u8;
-        match card {
-            'A' => streght = 14,
-            'K' => streght = 13,
-            'Q' => streght = 12,
-            'J' => {
-                if with_joker {streght = 1;} else {streght = 11;} 
-            },
-            'T' => streght = 10,
-            '9' => streght = 9,
-            '8' => streght = 8,
-            '7' => streght = 7,
-            '6' => streght = 6,
-            '5' => streght = 5,
-            '4' => streght = 4,
-            '3' => streght = 3,
-            '2' => streght = 2,
-            _   => streght = 0,  
-        };
-
-        streght
-    }
-    
+    Ruleset::new(ruleset.hand_size, ruleset.labels, ruleset.wildcards, ruleset.wildcard_own_rank)
+        .map(Some)
+        .map_err(|err| std::io::Error::other(format!("Those rules can not be played :( {err}")))
 }
 
-impl Ord for HandInfo {
-    fn cmp(&self, other: &Self) -> Ordering {
-        let mut order: Ordering = Ordering::Equal;
-        if self.h_type > other.h_type {
-            order = Ordering::Greater;
-        }
-        else if self.h_type < other.h_type {
-            order = Ordering::Less;
-        }
-        else {
-            let self_data_chars: Vec<char> = self.data.chars().collect();
-            let other_data_chars: Vec<char> = other.data.chars().collect();
-            if self_data_chars.len() == other_data_chars.len(){
-
-                let mut index_to_check: usize = 0;
-                let mut index_found: bool = false;
-
-                for i in 0 .. self_data_chars.len() {
-
-                    if self_data_chars[i] != other_data_chars[i] {
-                        index_found = true;
-                        index_to_check = i;
-                        break;
-                    }
-                }
-
-                if index_found {
-
-                    if HandInfo::get_relative_streght(self_data_chars[index_to_check], self.using_joker) > 
-                        HandInfo::get_relative_streght(other_data_chars[index_to_check], other.using_joker) {
-                        order = Ordering::Greater;
-                    }
-                    else {
-                        order = Ordering::Less;
-                    }
-                }
-                else {
-                    order = Ordering::Equal;
-                }
-            }
+fn algorithm(ruleset: &Ruleset) -> std::io::Result<u64> {
+    
+    let file:File = File::open("data/input.txt")?;
+    let reader: BufReader<File> = BufReader::new(file);
+    let mut hands: Vec<Hand> = Vec::new();
 
-        }
-        order
+    for (i, line) in reader.lines().enumerate(){
+        let line_str:String = line?;
+        hands.push(Hand::parse(&line_str, ruleset)
+            .map_err(|err| std::io::Error::other(format!("line {}: {err}", i + 1)))?);
     }
-}
 
-impl PartialOrd for HandInfo {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+    Ok(get_total_winnings(&mut hands))
 }
@@ -54,7 +54,16 @@
 
 // What is the sum of all of the gear ratios in your engine schematic?
 
-use std::{collections::HashMap, fs::File, io::{BufRead, BufReader}, usize};
+use std::{collections::HashMap, fs::File, io::{BufRead, BufReader}};
+
+use crate::schematic::{
+    adjacency::{TypeLine, get_asterix, get_boundary, get_coordinates_ranges, is_there_a_symbol, parse_number},
+    annotation::AnnotatedSchematic,
+    graph::AdjacencyGraph,
+    render::{render_ansi, render_html},
+    streaming::{checked_add_sum, process_schematic}};
+
+pub mod schematic;
 
 #[derive(Debug)]
 enum TypeRun {
@@ -62,25 +71,92 @@ enum TypeRun {
     SecondPart
 }
 
-enum TypeLine {
-    FirstLine,
-    NormalLine,
-    LastLine
-}
+fn main() -> std::io::Result<()>
+{
+    let args: Vec<String> = std::env::args().collect();
+
+    match args.get(1).map(|arg| arg.as_str()) {
+        // Print the schematic coloured by part numbers, not part numbers and gears
+        Some("render") => {
+            let schematic = AnnotatedSchematic::from_file("data/input.txt")?;
+            print!("{}", render_ansi(&schematic));
+        },
+        // Same as render but as a standalone html file with the adjacent symbols on hover
+        Some("render-html") => {
+            let output_path = args.get(2).map(|arg| arg.as_str()).unwrap_or("schematic.html");
+            let schematic = AnnotatedSchematic::from_file("data/input.txt")?;
+            std::fs::write(output_path, render_html(&schematic))?;
+            println!("Schematic written to {output_path}");
+        },
+        // Export the numbers <-> symbols graph
+        Some("graph-dot") => {
+            let schematic = AnnotatedSchematic::from_file("data/input.txt")?;
+            print!("{}", AdjacencyGraph::new(&schematic).to_dot());
+        },
+        Some("graph-csv") => {
+            let schematic = AnnotatedSchematic::from_file("data/input.txt")?;
+            print!("{}", AdjacencyGraph::new(&schematic).to_csv());
+        },
+        Some("symbols-of-number") => {
+            let coords = get_coords_arg(&args)?;
+            let schematic = AnnotatedSchematic::from_file("data/input.txt")?;
+            for symbol in AdjacencyGraph::new(&schematic).get_symbols_adjacent_to_number_at(coords) {
+                println!("'{}' at ({},{})", symbol.symbol, symbol.row, symbol.col);
+            }
+        },
+        Some("numbers-of-symbol") => {
+            let schematic = AnnotatedSchematic::from_file("data/input.txt")?;
+            let graph = AdjacencyGraph::new(&schematic);
+
+            // Either the coordinates of one symbol or a symbol kind like '#'
+            let numbers = match args.get(2).and_then(|arg| arg.chars().next()) {
+                Some(symbol) if args.len() == 3 && !symbol.is_ascii_digit() =>
+                    graph.get_numbers_adjacent_to_symbol(symbol),
+                _ => graph.get_numbers_adjacent_to_symbol_at(get_coords_arg(&args)?),
+            };
+
+            for number in numbers {
+                println!("{} at ({},{})", number.value, number.row, number.start_col);
+            }
+        },
+        Some("clusters") => {
+            let schematic = AnnotatedSchematic::from_file("data/input.txt")?;
+            let graph = AdjacencyGraph::new(&schematic);
+
+            for (i, cluster) in graph.get_part_clusters().iter().enumerate() {
+                let values: Vec<String> = cluster.iter()
+                    .map(|number_i| graph.numbers()[*number_i].value.to_string())
+                    .collect();
+                println!("Cluster {i}: {}", values.join(" "));
+            }
+        },
+        // Both parts in one pass with bounded memory, meant for huge schematics
+        Some("stream") => {
+            let input_path = args.get(2).map(|arg| arg.as_str()).unwrap_or("data/input.txt");
+            let sums = process_schematic(BufReader::new(File::open(input_path)?))?;
+            println!("Processed {} lines", sums.lines);
+            println!("The total sum for the quest for the {:?} is: {}", TypeRun::FirstPart, sums.part_numbers_sum);
+            println!("The total sum for the quest for the {:?} is: {}", TypeRun::SecondPart, sums.gear_ratios_sum);
+        },
+        _ => {
+            algorithm(TypeRun::FirstPart)?;
+            algorithm(TypeRun::SecondPart)?;
+        },
+    }
 
-#[derive(Debug)]
-enum TypeCoordinateRage {
-    Previuos,
-    Current,
-    Next
+    Ok(()) 
 }
 
-fn main() -> std::io::Result<()>
+fn get_coords_arg(args: &[String]) -> std::io::Result<(usize, usize)>
 {
-    algorithm(TypeRun::FirstPart)?;
-    algorithm(TypeRun::SecondPart)?;
+    let coords = args.get(2 ..4).map(|values| values.iter()
+        .map(|value| value.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>());
 
-    Ok(()) 
+    match coords {
+        Some(Ok(values)) => Ok((values[0], values[1])),
+        _ => Err(std::io::Error::other("Expected the coordinates as <row> <col> :(")),
+    }
 }
 
 fn algorithm (type_run: TypeRun) -> std::io::Result<()> 
@@ -95,8 +171,8 @@ fn algorithm (type_run: TypeRun) -> std::io::Result<()>
     let mut prev_line: String = "".to_string();
     let mut pre_prev_line: String = "".to_string();
 
-    let mut total_sum: u32 = 0;
-    let mut map_asterix: HashMap<(usize, usize), Vec<u32>> = HashMap::new(); // only for part 2
+    let mut total_sum: u64 = 0;
+    let mut map_asterix: HashMap<(usize, usize), Vec<u64>> = HashMap::new(); // only for part 2
 
     let mut line_index: usize = 0; // Only for part 2
     for (i, line) in reader.lines().enumerate() {
@@ -105,18 +181,19 @@ fn algorithm (type_run: TypeRun) -> std::io::Result<()>
 
         if 0 < i {
 
-            let mut type_line: TypeLine = TypeLine::NormalLine;
+            let mut type_line: TypeLine = TypeLine::Normal;
             
             if 1 == i {
-                type_line = TypeLine::FirstLine;
+                type_line = TypeLine::First;
             }
 
             if matches!(type_run, TypeRun::FirstPart){
-                total_sum += check_lines(&pre_prev_line, &prev_line, &curr_line, &type_line);
+                let sum = check_lines(line_index, &pre_prev_line, &prev_line, &curr_line, &type_line)?;
+                total_sum = checked_add_sum(total_sum, sum, "part numbers")?;
             }
             else {
                 check_lines_2(line_index, &mut map_asterix,&pre_prev_line, &prev_line, 
-                    &curr_line, &type_line);
+                    &curr_line, &type_line)?;
             }
 
             line_index = i;
@@ -128,18 +205,21 @@ fn algorithm (type_run: TypeRun) -> std::io::Result<()>
 
     // This part is done to consider also the last line
     if matches!(type_run, TypeRun::FirstPart){
-        total_sum += check_lines(&pre_prev_line, &prev_line, &curr_line, 
-            &TypeLine::LastLine);
+        let sum = check_lines(line_index, &pre_prev_line, &prev_line, &curr_line, 
+            &TypeLine::Last)?;
+        total_sum = checked_add_sum(total_sum, sum, "part numbers")?;
     }
     else {
         check_lines_2(line_index,&mut map_asterix,&pre_prev_line, &prev_line, &curr_line, 
-            &TypeLine::LastLine);
+            &TypeLine::Last)?;
 
         // checking with asterix has exactly two part numbers 
-        for (_, values) in &map_asterix {
+        for values in map_asterix.values() {
             
             if values.len() == 2 {
-                total_sum += values[0] * values[1];
+                let gear_ratio = values[0].checked_mul(values[1])
+                    .ok_or_else(|| std::io::Error::other("A gear ratio does not fit in a u64 :("))?;
+                total_sum = checked_add_sum(total_sum, gear_ratio, "gear ratios")?;
             }
         }
     }
@@ -149,7 +229,8 @@ fn algorithm (type_run: TypeRun) -> std::io::Result<()>
     Ok(())
 }
 
-fn check_lines(line_prev: &String, line_curr: &String, line_next: &String, type_line: &TypeLine) -> u32
+fn check_lines(line_index: usize, line_prev: &str, line_curr: &str, line_next: &str, type_line: &TypeLine)
+    -> std::io::Result<u64>
 {
 
     let line_prev_chars: Vec<char> = line_prev.chars().collect();
@@ -157,27 +238,29 @@ fn check_lines(line_prev: &String, line_curr: &String, line_next: &String, type_
     let line_next_chars: Vec<char> = line_next.chars().collect();
     let mut pos = 0;
 
-    let mut sum: u32 = 0;
+    let mut sum: u64 = 0;
 
     while pos < line_curr_chars.len(){
         
-        if line_curr_chars[pos].is_digit(10) {
+        if line_curr_chars[pos].is_ascii_digit() {
             let (pos_start, pos_end) = get_boundary(&line_curr_chars, pos);
-            let ranges = get_coordinates_ranges((pos_start, pos_end), &type_line, line_curr_chars.len());
+            let ranges = get_coordinates_ranges((pos_start, pos_end), type_line,
+                (line_prev_chars.len(), line_curr_chars.len(), line_next_chars.len()));
             pos = pos_end;
 
             if is_there_a_symbol(&ranges, &line_prev_chars, &line_curr_chars, &line_next_chars){
-                sum += line_curr[pos_start..=pos_end].parse::<u32>().unwrap();
+                let number = parse_number(&line_curr_chars, (pos_start, pos_end), line_index)?;
+                sum = checked_add_sum(sum, number, "part numbers")?;
             }
         }
         pos += 1;
     }
 
-    sum
+    Ok(sum)
 }
 
-fn check_lines_2(line_index: usize, map_asterix: & mut HashMap<(usize, usize), Vec<u32>>, 
-    line_prev: &String, line_curr: &String, line_next: &String, type_line: &TypeLine)
+fn check_lines_2(line_index: usize, map_asterix: & mut HashMap<(usize, usize), Vec<u64>>, 
+    line_prev: &str, line_curr: &str, line_next: &str, type_line: &TypeLine) -> std::io::Result<()>
 {
 
     let line_prev_chars: Vec<char> = line_prev.chars().collect();
@@ -187,9 +270,10 @@ fn check_lines_2(line_index: usize, map_asterix: & mut HashMap<(usize, usize), V
 
     while pos < line_curr_chars.len(){
         
-        if line_curr_chars[pos].is_digit(10) {
+        if line_curr_chars[pos].is_ascii_digit() {
             let (pos_start, pos_end) = get_boundary(&line_curr_chars, pos);
-            let ranges = get_coordinates_ranges((pos_start, pos_end), &type_line, line_curr_chars.len());
+            let ranges = get_coordinates_ranges((pos_start, pos_end), type_line,
+                (line_prev_chars.len(), line_curr_chars.len(), line_next_chars.len()));
             pos = pos_end;
 
             let arterix_coords = get_asterix(line_index, &ranges, &line_prev_chars, 
@@ -197,7 +281,7 @@ fn check_lines_2(line_index: usize, map_asterix: & mut HashMap<(usize, usize), V
             
             // Adding to map
             for (x, y) in arterix_coords {
-                let number = line_curr[pos_start..=pos_end].parse::<u32>().unwrap();
+                let number = parse_number(&line_curr_chars, (pos_start, pos_end), line_index)?;
 
                 // In case the entry exists at the number to end of vector
                 if let Some(values) = map_asterix.get_mut(&(x, y)) {
@@ -211,122 +295,30 @@ fn check_lines_2(line_index: usize, map_asterix: & mut HashMap<(usize, usize), V
         }
         pos += 1;
     }
-}
-
-fn get_boundary(line_chars: &Vec<char>, intial_pose: usize) -> (usize, usize)
-{
-    
-    let mut index = intial_pose+1;
-    let mut end_pose = intial_pose;
-
-    while index < line_chars.len() {
-
-        if !line_chars[index].is_digit(10){
-            break;
-        }
-
-        end_pose = index;
-        index += 1;
-    }
-
-    (intial_pose, end_pose)
-}
-
-fn get_coordinates_ranges(number_pose: (usize, usize), type_line: &TypeLine, curr_len: usize) 
-        -> Vec<(TypeCoordinateRage, (usize, usize))>
-{
-    
-    let (start_pose, end_pose) = number_pose;
-    
-    let mut vector_result : Vec<(TypeCoordinateRage, (usize, usize))> = Vec::new();
-
-    let mut index_begin = start_pose;
-    let mut index_end = end_pose;
-
-    //current line
-    if start_pose > 0 {
-        index_begin -= 1;
-        vector_result.push((TypeCoordinateRage::Current, (start_pose-1, start_pose-1)));
-    }
 
-    if end_pose < curr_len - 1{
-        index_end += 1;
-        vector_result.push((TypeCoordinateRage::Current, (end_pose+1, end_pose+1)));
-    }
-
-    // prev line
-    if !matches!(type_line, TypeLine::FirstLine) {
-        vector_result.push((TypeCoordinateRage::Previuos, (index_begin, index_end)));
-    }
-
-    // next line
-    if !matches!(type_line, TypeLine::LastLine) {
-        vector_result.push((TypeCoordinateRage::Next, (index_begin, index_end)));
-    }
-
-    vector_result
+    Ok(())
 }
 
-fn is_there_a_symbol(ranges: &Vec<(TypeCoordinateRage, (usize, usize))>, 
-        prev_chars: &Vec<char>, curr_chars: &Vec<char>, next_chars: &Vec<char>) -> bool
-{
-    
-    for (type_coordinate, (start_index, end_index)) in ranges {
-        
-        let char_vector : &Vec<char>;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if matches!(type_coordinate, TypeCoordinateRage::Previuos){
-            char_vector = prev_chars;
-        }
-        else if matches!(type_coordinate, TypeCoordinateRage::Current){
-            char_vector = curr_chars;
-        }
-        else {
-            char_vector = next_chars;
-        }
+    #[test]
+    fn part_numbers_of_a_line() {
+        let sum = check_lines(2, "...*......", "..35..633.", "......#...", &TypeLine::Normal).unwrap();
 
-        for i in *start_index ..=*end_index {
-            if char_is_symbol(char_vector[i]){
-                return true;
-            }
-        }
+        assert_eq!(sum, 668);
     }
 
-    false
-}
+    #[test]
+    fn numbers_too_big_for_a_u64_are_an_error() {
+        let line = "..12345678901234567890123.";
 
-fn char_is_symbol (character: char) -> bool
-{
-    return !character.is_alphanumeric() && character != '.';
-}
+        let err = check_lines(4, "..*", line, "", &TypeLine::Normal).expect_err("number overflows a u64");
+        assert_eq!(err.to_string(), "The number 12345678901234567890123 at (4,2) does not fit in a u64 :(");
 
-fn get_asterix(line_index: usize, ranges: &Vec<(TypeCoordinateRage, (usize, usize))>, 
-        prev_chars: &Vec<char>, curr_chars: &Vec<char>, next_chars: &Vec<char>) -> Vec<(usize, usize)>
-{
-    let mut asterix_coords: Vec<(usize, usize)> = Vec::new();
-    for (type_coordinate, (start_index, end_index)) in ranges {
-        
-        let char_vector : &Vec<char>;
-        let mut line_index_asterix = line_index;
-
-        if matches!(type_coordinate, TypeCoordinateRage::Previuos){
-            char_vector = prev_chars;
-            line_index_asterix -= 1;
-        }
-        else if matches!(type_coordinate, TypeCoordinateRage::Current){
-            char_vector = curr_chars;
-        }
-        else {
-            char_vector = next_chars;
-            line_index_asterix += 1;
-        }
-
-        for i in *start_index ..=*end_index {
-            if char_vector[i] == '*'{
-                asterix_coords.push((line_index_asterix, i));
-            }
-        }
+        let mut map_asterix: HashMap<(usize, usize), Vec<u64>> = HashMap::new();
+        let err = check_lines_2(4, &mut map_asterix, "..*", line, "", &TypeLine::Normal).expect_err("number overflows a u64");
+        assert_eq!(err.to_string(), "The number 12345678901234567890123 at (4,2) does not fit in a u64 :(");
     }
-
-    asterix_coords
 }
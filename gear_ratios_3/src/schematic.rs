@@ -0,0 +1,5 @@
+pub mod adjacency;
+pub mod annotation;
+pub mod graph;
+pub mod render;
+pub mod streaming;
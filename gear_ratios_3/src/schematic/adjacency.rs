@@ -0,0 +1,167 @@
+use std::cmp::min;
+
+pub enum TypeLine {
+    First,
+    Normal,
+    Last
+}
+
+#[derive(Debug)]
+pub enum TypeCoordinateRage {
+    Previuos,
+    Current,
+    Next
+}
+
+pub fn get_boundary(line_chars: &[char], intial_pose: usize) -> (usize, usize)
+{
+
+    let mut index = intial_pose+1;
+    let mut end_pose = intial_pose;
+
+    while index < line_chars.len() {
+
+        if !line_chars[index].is_ascii_digit(){
+            break;
+        }
+
+        end_pose = index;
+        index += 1;
+    }
+
+    (intial_pose, end_pose)
+}
+
+// Value of the digits found by get_boundary, an error when they do not fit in a u64
+pub fn parse_number(line_chars: &[char], number_pose: (usize, usize), line_index: usize) -> std::io::Result<u64>
+{
+    let (start_pose, end_pose) = number_pose;
+    let digits: String = line_chars[start_pose..=end_pose].iter().collect();
+
+    digits.parse::<u64>().map_err(|_| std::io::Error::other(
+        format!("The number {digits} at ({line_index},{start_pose}) does not fit in a u64 :(")))
+}
+
+// lines_len holds the length of the previous, current and next lines, which
+// may differ, so every range is clamped to the line it is going to be read from
+pub fn get_coordinates_ranges(number_pose: (usize, usize), type_line: &TypeLine, lines_len: (usize, usize, usize))
+        -> Vec<(TypeCoordinateRage, (usize, usize))>
+{
+
+    let (start_pose, end_pose) = number_pose;
+    let (prev_len, curr_len, next_len) = lines_len;
+
+    let mut vector_result : Vec<(TypeCoordinateRage, (usize, usize))> = Vec::new();
+
+    let mut index_begin = start_pose;
+    // The diagonal after the number may exist on a longer line above or below,
+    // even when the number ends the current line
+    let index_end = end_pose + 1;
+
+    //current line
+    if start_pose > 0 {
+        index_begin -= 1;
+        vector_result.push((TypeCoordinateRage::Current, (start_pose-1, start_pose-1)));
+    }
+
+    if index_end < curr_len {
+        vector_result.push((TypeCoordinateRage::Current, (index_end, index_end)));
+    }
+
+    // prev line
+    if !matches!(type_line, TypeLine::First) && index_begin < prev_len {
+        vector_result.push((TypeCoordinateRage::Previuos, (index_begin, min(index_end, prev_len - 1))));
+    }
+
+    // next line
+    if !matches!(type_line, TypeLine::Last) && index_begin < next_len {
+        vector_result.push((TypeCoordinateRage::Next, (index_begin, min(index_end, next_len - 1))));
+    }
+
+    vector_result
+}
+
+pub fn is_there_a_symbol(ranges: &[(TypeCoordinateRage, (usize, usize))],
+        prev_chars: &[char], curr_chars: &[char], next_chars: &[char]) -> bool
+{
+
+    for (type_coordinate, (start_index, end_index)) in ranges {
+
+        let char_vector : &[char] = match type_coordinate {
+            TypeCoordinateRage::Previuos => prev_chars,
+            TypeCoordinateRage::Current => curr_chars,
+            TypeCoordinateRage::Next => next_chars,
+        };
+
+        if char_vector[*start_index ..=*end_index].iter().any(|c| char_is_symbol(*c)){
+            return true;
+        }
+    }
+
+    false
+}
+
+pub fn char_is_symbol (character: char) -> bool
+{
+    !character.is_alphanumeric() && character != '.' && !character.is_whitespace()
+}
+
+pub fn get_asterix(line_index: usize, ranges: &[(TypeCoordinateRage, (usize, usize))],
+        prev_chars: &[char], curr_chars: &[char], next_chars: &[char]) -> Vec<(usize, usize)>
+{
+    get_symbols(line_index, ranges, prev_chars, curr_chars, next_chars).into_iter()
+        .filter(|(_, _, symbol)| *symbol == '*')
+        .map(|(x, y, _)| (x, y))
+        .collect()
+}
+
+// Same walk as get_asterix but keeping every symbol found with its coordinates
+pub fn get_symbols(line_index: usize, ranges: &[(TypeCoordinateRage, (usize, usize))],
+        prev_chars: &[char], curr_chars: &[char], next_chars: &[char]) -> Vec<(usize, usize, char)>
+{
+    let mut symbol_coords: Vec<(usize, usize, char)> = Vec::new();
+    for (type_coordinate, (start_index, end_index)) in ranges {
+
+        let (char_vector, line_index_symbol) : (&[char], usize) = match type_coordinate {
+            TypeCoordinateRage::Previuos => (prev_chars, line_index - 1),
+            TypeCoordinateRage::Current => (curr_chars, line_index),
+            TypeCoordinateRage::Next => (next_chars, line_index + 1),
+        };
+
+        for (i, character) in char_vector.iter().enumerate().take(*end_index + 1).skip(*start_index) {
+            if char_is_symbol(*character){
+                symbol_coords.push((line_index_symbol, i, *character));
+            }
+        }
+    }
+
+    symbol_coords
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(rows: &[&str]) -> Vec<Vec<char>> {
+        rows.iter().map(|row| row.chars().collect()).collect()
+    }
+
+    #[test]
+    fn number_ending_a_short_line_reaches_the_diagonal_of_longer_lines() {
+        let rows = lines(&[".....+", "..592", "......"]);
+        let ranges = get_coordinates_ranges((2, 4), &TypeLine::Normal, (6, 5, 6));
+
+        assert!(is_there_a_symbol(&ranges, &rows[0], &rows[1], &rows[2]));
+        assert_eq!(get_symbols(1, &ranges, &rows[0], &rows[1], &rows[2]), vec![(0, 5, '+')]);
+    }
+
+    #[test]
+    fn ranges_are_clamped_to_shorter_lines() {
+        let rows = lines(&[".", "..592.", "...#"]);
+        let ranges = get_coordinates_ranges((2, 4), &TypeLine::Normal, (1, 6, 4));
+
+        // The previous line ends before the number, the next one before its diagonal
+        assert!(ranges.iter().all(|(type_coordinate, _)| !matches!(type_coordinate, TypeCoordinateRage::Previuos)));
+        assert_eq!(get_symbols(1, &ranges, &rows[0], &rows[1], &rows[2]), vec![(2, 3, '#')]);
+    }
+}
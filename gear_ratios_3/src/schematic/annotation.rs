@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use crate::schematic::adjacency::{TypeLine, get_boundary, get_coordinates_ranges, get_symbols, parse_number};
+
+#[derive(Debug, Clone)]
+pub struct NumberInfo {
+    pub row: usize,
+    pub start_col: usize,
+    pub end_col: usize,
+    pub value: u64,
+    pub adjacent_symbols: Vec<(usize, usize, char)>,
+}
+
+impl NumberInfo {
+
+    pub fn is_part_number(&self) -> bool {
+        !self.adjacent_symbols.is_empty()
+    }
+}
+
+pub struct AnnotatedSchematic {
+    pub lines: Vec<Vec<char>>,
+    pub numbers: Vec<NumberInfo>,
+    // Every '*' touched by at least one number, with the indexes of those numbers
+    pub asterix: HashMap<(usize, usize), Vec<usize>>,
+}
+
+impl AnnotatedSchematic {
+
+    pub fn from_file(path: &str) -> std::io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let lines = reader.lines().collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_lines(&lines)
+    }
+
+    pub fn from_lines(lines_str: &[String]) -> std::io::Result<Self> {
+        let lines: Vec<Vec<char>> = lines_str.iter().map(|line| line.chars().collect()).collect();
+        let empty_line: Vec<char> = Vec::new();
+
+        let mut numbers: Vec<NumberInfo> = Vec::new();
+        let mut asterix: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+
+        for (line_index, line_curr_chars) in lines.iter().enumerate() {
+
+            let type_line = if line_index == 0 {
+                TypeLine::First
+            }
+            else if line_index == lines.len() - 1 {
+                TypeLine::Last
+            }
+            else {
+                TypeLine::Normal
+            };
+
+            let line_prev_chars = if line_index > 0 {&lines[line_index - 1]} else {&empty_line};
+            let line_next_chars = lines.get(line_index + 1).unwrap_or(&empty_line);
+
+            let mut pos = 0;
+            while pos < line_curr_chars.len() {
+
+                if line_curr_chars[pos].is_ascii_digit() {
+                    let (pos_start, pos_end) = get_boundary(line_curr_chars, pos);
+                    let ranges = get_coordinates_ranges((pos_start, pos_end), &type_line,
+                        (line_prev_chars.len(), line_curr_chars.len(), line_next_chars.len()));
+                    pos = pos_end;
+
+                    let adjacent_symbols = get_symbols(line_index, &ranges, line_prev_chars,
+                        line_curr_chars, line_next_chars);
+
+                    for (x, y, symbol) in &adjacent_symbols {
+                        if *symbol == '*' {
+                            asterix.entry((*x, *y)).or_default().push(numbers.len());
+                        }
+                    }
+
+                    numbers.push(NumberInfo {
+                        row: line_index,
+                        start_col: pos_start,
+                        end_col: pos_end,
+                        value: parse_number(line_curr_chars, (pos_start, pos_end), line_index)?,
+                        adjacent_symbols,
+                    });
+                }
+                pos += 1;
+            }
+        }
+
+        Ok(AnnotatedSchematic { lines, numbers, asterix })
+    }
+
+    pub fn is_gear(&self, coords: (usize, usize)) -> bool {
+        self.asterix.get(&coords).is_some_and(|numbers| numbers.len() == 2)
+    }
+
+    // The product of two u64 always fits in a u128
+    pub fn get_gear_ratio(&self, coords: (usize, usize)) -> Option<u128> {
+        if !self.is_gear(coords) {
+            return None;
+        }
+
+        let numbers = &self.asterix[&coords];
+        Some(self.numbers[numbers[0]].value as u128 * self.numbers[numbers[1]].value as u128)
+    }
+
+    // For each cell the index of the number covering it, if any
+    pub fn get_number_cells(&self) -> HashMap<(usize, usize), usize> {
+        let mut number_cells: HashMap<(usize, usize), usize> = HashMap::new();
+
+        for (i, number) in self.numbers.iter().enumerate() {
+            for col in number.start_col ..=number.end_col {
+                number_cells.insert((number.row, col), i);
+            }
+        }
+
+        number_cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_lines(rows: &[&str]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    #[test]
+    fn numbers_too_big_for_a_u64_are_an_error() {
+        let lines = to_lines(&["..12345678901234567890123", "*........................"]);
+        let err = AnnotatedSchematic::from_lines(&lines).err().expect("23 digits do not fit in a u64");
+
+        assert!(err.to_string().contains("(0,2)"));
+    }
+
+    #[test]
+    fn gear_ratios_of_large_numbers_do_not_overflow() {
+        let lines = to_lines(&["18446744073709551615*18446744073709551615"]);
+        let schematic = AnnotatedSchematic::from_lines(&lines).unwrap();
+
+        assert_eq!(schematic.get_gear_ratio((0, 20)), Some(u64::MAX as u128 * u64::MAX as u128));
+    }
+}
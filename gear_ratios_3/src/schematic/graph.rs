@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+
+use crate::schematic::annotation::{AnnotatedSchematic, NumberInfo};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolNode {
+    pub row: usize,
+    pub col: usize,
+    pub symbol: char,
+}
+
+// Bipartite graph between the numbers of the schematic and the symbols they touch
+pub struct AdjacencyGraph<'a> {
+    schematic: &'a AnnotatedSchematic,
+    symbols: Vec<SymbolNode>,
+    number_to_symbols: Vec<Vec<usize>>,
+    symbol_to_numbers: Vec<Vec<usize>>,
+    symbol_index: HashMap<(usize, usize), usize>,
+    number_cells: HashMap<(usize, usize), usize>,
+}
+
+impl<'a> AdjacencyGraph<'a> {
+
+    pub fn new(schematic: &'a AnnotatedSchematic) -> Self {
+        let mut symbols: Vec<SymbolNode> = Vec::new();
+        let mut symbol_index: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut number_to_symbols: Vec<Vec<usize>> = Vec::new();
+        let mut symbol_to_numbers: Vec<Vec<usize>> = Vec::new();
+
+        for (number_i, number) in schematic.numbers.iter().enumerate() {
+
+            let mut symbols_of_number: Vec<usize> = Vec::new();
+
+            for (row, col, symbol) in &number.adjacent_symbols {
+                let symbol_i = *symbol_index.entry((*row, *col)).or_insert_with(|| {
+                    symbols.push(SymbolNode { row: *row, col: *col, symbol: *symbol });
+                    symbol_to_numbers.push(Vec::new());
+                    symbols.len() - 1
+                });
+
+                symbol_to_numbers[symbol_i].push(number_i);
+                symbols_of_number.push(symbol_i);
+            }
+
+            number_to_symbols.push(symbols_of_number);
+        }
+
+        AdjacencyGraph {
+            schematic,
+            symbols,
+            number_to_symbols,
+            symbol_to_numbers,
+            symbol_index,
+            number_cells: schematic.get_number_cells(),
+        }
+    }
+
+    pub fn numbers(&self) -> &[NumberInfo] {
+        &self.schematic.numbers
+    }
+
+    pub fn symbols(&self) -> &[SymbolNode] {
+        &self.symbols
+    }
+
+    // (r,c) can be any cell covered by the number, not only its first digit
+    pub fn get_symbols_adjacent_to_number_at(&self, coords: (usize, usize)) -> Vec<&SymbolNode> {
+        match self.number_cells.get(&coords) {
+            Some(number_i) => self.number_to_symbols[*number_i].iter()
+                .map(|symbol_i| &self.symbols[*symbol_i])
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn get_numbers_adjacent_to_symbol_at(&self, coords: (usize, usize)) -> Vec<&NumberInfo> {
+        match self.symbol_index.get(&coords) {
+            Some(symbol_i) => self.symbol_to_numbers[*symbol_i].iter()
+                .map(|number_i| &self.schematic.numbers[*number_i])
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    // Every number touching at least one symbol of the given kind (e.g. every '#')
+    pub fn get_numbers_adjacent_to_symbol(&self, symbol: char) -> Vec<&NumberInfo> {
+        self.schematic.numbers.iter().enumerate()
+            .filter(|(number_i, _)| self.number_to_symbols[*number_i].iter()
+                .any(|symbol_i| self.symbols[*symbol_i].symbol == symbol))
+            .map(|(_, number)| number)
+            .collect()
+    }
+
+    // Groups of part numbers linked together through shared symbols,
+    // each group holding the indexes of its numbers in ascending order
+    pub fn get_part_clusters(&self) -> Vec<Vec<usize>> {
+        let mut visited: Vec<bool> = vec![false; self.schematic.numbers.len()];
+        let mut clusters: Vec<Vec<usize>> = Vec::new();
+
+        for start in 0 .. self.schematic.numbers.len() {
+
+            if visited[start] || self.number_to_symbols[start].is_empty() {
+                continue;
+            }
+
+            let mut cluster: Vec<usize> = Vec::new();
+            let mut stack: Vec<usize> = vec![start];
+            visited[start] = true;
+
+            while let Some(number_i) = stack.pop() {
+                cluster.push(number_i);
+
+                for symbol_i in &self.number_to_symbols[number_i] {
+                    for neighbour in &self.symbol_to_numbers[*symbol_i] {
+                        if !visited[*neighbour] {
+                            visited[*neighbour] = true;
+                            stack.push(*neighbour);
+                        }
+                    }
+                }
+            }
+
+            cluster.sort();
+            clusters.push(cluster);
+        }
+
+        clusters
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut result = String::from("graph schematic {\n");
+
+        for (number_i, number) in self.schematic.numbers.iter().enumerate() {
+            result.push_str(&format!("    n{number_i} [label=\"{} ({},{})\", shape=box];\n",
+                number.value, number.row, number.start_col));
+        }
+
+        for (symbol_i, symbol) in self.symbols.iter().enumerate() {
+            result.push_str(&format!("    s{symbol_i} [label=\"{} ({},{})\", shape=circle];\n",
+                escape_dot(symbol.symbol), symbol.row, symbol.col));
+        }
+
+        for (number_i, symbols_of_number) in self.number_to_symbols.iter().enumerate() {
+            for symbol_i in symbols_of_number {
+                result.push_str(&format!("    n{number_i} -- s{symbol_i};\n"));
+            }
+        }
+
+        result.push_str("}\n");
+        result
+    }
+
+    // One line per edge of the graph
+    pub fn to_csv(&self) -> String {
+        let mut result = String::from("number,number_row,number_col,symbol,symbol_row,symbol_col\n");
+
+        for (number_i, symbols_of_number) in self.number_to_symbols.iter().enumerate() {
+            let number = &self.schematic.numbers[number_i];
+
+            for symbol_i in symbols_of_number {
+                let symbol = &self.symbols[*symbol_i];
+                result.push_str(&format!("{},{},{},{},{},{}\n", number.value, number.row, number.start_col,
+                    escape_csv(symbol.symbol), symbol.row, symbol.col));
+            }
+        }
+
+        result
+    }
+}
+
+fn escape_dot(symbol: char) -> String
+{
+    match symbol {
+        '"' | '\\' => format!("\\{symbol}"),
+        _ => symbol.to_string(),
+    }
+}
+
+fn escape_csv(symbol: char) -> String
+{
+    match symbol {
+        '"' => "\"\"\"\"".to_string(),
+        ',' => "\",\"".to_string(),
+        _ => symbol.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2 and 3 share a gear, 5 touches '#' and '$', 7 and 8 share a ',' and 9 touches nothing
+    const ROWS: [&str; 4] = ["2*3.#.", "....5$", "7,8...", "....9."];
+
+    fn schematic() -> AnnotatedSchematic {
+        let lines: Vec<String> = ROWS.iter().map(|row| row.to_string()).collect();
+
+        AnnotatedSchematic::from_lines(&lines).unwrap()
+    }
+
+    fn values(numbers: &[&NumberInfo]) -> Vec<u64> {
+        numbers.iter().map(|number| number.value).collect()
+    }
+
+    #[test]
+    fn symbols_of_a_number() {
+        let schematic = schematic();
+        let graph = AdjacencyGraph::new(&schematic);
+
+        assert_eq!(graph.get_symbols_adjacent_to_number_at((1, 4)), vec![
+            &SymbolNode { row: 1, col: 5, symbol: '$' },
+            &SymbolNode { row: 0, col: 4, symbol: '#' },
+        ]);
+        assert_eq!(graph.get_symbols_adjacent_to_number_at((0, 2)), vec![&SymbolNode { row: 0, col: 1, symbol: '*' }]);
+        assert!(graph.get_symbols_adjacent_to_number_at((3, 4)).is_empty());
+        assert!(graph.get_symbols_adjacent_to_number_at((3, 0)).is_empty());
+    }
+
+    #[test]
+    fn numbers_of_a_symbol() {
+        let schematic = schematic();
+        let graph = AdjacencyGraph::new(&schematic);
+
+        assert_eq!(values(&graph.get_numbers_adjacent_to_symbol_at((0, 1))), vec![2, 3]);
+        assert_eq!(values(&graph.get_numbers_adjacent_to_symbol_at((2, 1))), vec![7, 8]);
+        assert!(graph.get_numbers_adjacent_to_symbol_at((0, 0)).is_empty());
+
+        assert_eq!(values(&graph.get_numbers_adjacent_to_symbol('$')), vec![5]);
+        assert_eq!(values(&graph.get_numbers_adjacent_to_symbol('*')), vec![2, 3]);
+        assert!(graph.get_numbers_adjacent_to_symbol('@').is_empty());
+    }
+
+    #[test]
+    fn clusters_of_part_numbers() {
+        let schematic = schematic();
+        let graph = AdjacencyGraph::new(&schematic);
+
+        assert_eq!(graph.symbols().len(), 4);
+        assert_eq!(graph.get_part_clusters(), vec![vec![0, 1], vec![2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn dot_output() {
+        let schematic = schematic();
+
+        assert_eq!(AdjacencyGraph::new(&schematic).to_dot(), "\
+graph schematic {
+    n0 [label=\"2 (0,0)\", shape=box];
+    n1 [label=\"3 (0,2)\", shape=box];
+    n2 [label=\"5 (1,4)\", shape=box];
+    n3 [label=\"7 (2,0)\", shape=box];
+    n4 [label=\"8 (2,2)\", shape=box];
+    n5 [label=\"9 (3,4)\", shape=box];
+    s0 [label=\"* (0,1)\", shape=circle];
+    s1 [label=\"$ (1,5)\", shape=circle];
+    s2 [label=\"# (0,4)\", shape=circle];
+    s3 [label=\", (2,1)\", shape=circle];
+    n0 -- s0;
+    n1 -- s0;
+    n2 -- s1;
+    n2 -- s2;
+    n3 -- s3;
+    n4 -- s3;
+}
+");
+    }
+
+    #[test]
+    fn csv_output() {
+        let schematic = schematic();
+
+        assert_eq!(AdjacencyGraph::new(&schematic).to_csv(), "\
+number,number_row,number_col,symbol,symbol_row,symbol_col
+2,0,0,*,0,1
+3,0,2,*,0,1
+5,1,4,$,1,5
+5,1,4,#,0,4
+7,2,0,\",\",2,1
+8,2,2,\",\",2,1
+");
+    }
+}
@@ -0,0 +1,191 @@
+use crate::schematic::adjacency::char_is_symbol;
+use crate::schematic::annotation::{AnnotatedSchematic, NumberInfo};
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_PART_NUMBER: &str = "\x1b[32m";
+const ANSI_NOT_PART_NUMBER: &str = "\x1b[31m";
+const ANSI_GEAR: &str = "\x1b[1;33m";
+const ANSI_SYMBOL: &str = "\x1b[36m";
+const ANSI_DOT: &str = "\x1b[2m";
+
+enum TypeCell<'a> {
+    PartNumber(&'a NumberInfo),
+    NotPartNumber(&'a NumberInfo),
+    Gear(u128),
+    Symbol,
+    Empty,
+}
+
+fn get_cell_type<'a>(schematic: &'a AnnotatedSchematic, number_cell: Option<&usize>,
+        coords: (usize, usize), character: char) -> TypeCell<'a>
+{
+    if let Some(index) = number_cell {
+        let number = &schematic.numbers[*index];
+        if number.is_part_number() {TypeCell::PartNumber(number)} else {TypeCell::NotPartNumber(number)}
+    }
+    else if let Some(ratio) = schematic.get_gear_ratio(coords) {
+        TypeCell::Gear(ratio)
+    }
+    else if char_is_symbol(character) {
+        TypeCell::Symbol
+    }
+    else {
+        TypeCell::Empty
+    }
+}
+
+pub fn render_ansi(schematic: &AnnotatedSchematic) -> String
+{
+    let number_cells = schematic.get_number_cells();
+    let mut result = String::new();
+
+    for (row, line) in schematic.lines.iter().enumerate() {
+        for (col, character) in line.iter().enumerate() {
+
+            let color = match get_cell_type(schematic, number_cells.get(&(row, col)), (row, col), *character) {
+                TypeCell::PartNumber(_) => ANSI_PART_NUMBER,
+                TypeCell::NotPartNumber(_) => ANSI_NOT_PART_NUMBER,
+                TypeCell::Gear(_) => ANSI_GEAR,
+                TypeCell::Symbol => ANSI_SYMBOL,
+                TypeCell::Empty => ANSI_DOT,
+            };
+
+            result.push_str(color);
+            result.push(*character);
+            result.push_str(ANSI_RESET);
+        }
+        result.push('\n');
+    }
+
+    result
+}
+
+pub fn render_html(schematic: &AnnotatedSchematic) -> String
+{
+    let number_cells = schematic.get_number_cells();
+    let mut body = String::new();
+
+    for (row, line) in schematic.lines.iter().enumerate() {
+        let mut col = 0;
+        while col < line.len() {
+
+            match get_cell_type(schematic, number_cells.get(&(row, col)), (row, col), line[col]) {
+                TypeCell::PartNumber(number) | TypeCell::NotPartNumber(number) => {
+                    // The digits as written, so numbers like 007 keep their width
+                    let class = if number.is_part_number() {"part"} else {"not-part"};
+                    let digits: String = line[number.start_col..=number.end_col].iter().collect();
+                    body.push_str(&format!("<span class=\"{class}\" title=\"{}\">{digits}</span>",
+                        escape_html(&get_number_title(number))));
+                    col = number.end_col;
+                },
+                TypeCell::Gear(ratio) => {
+                    body.push_str(&format!("<span class=\"gear\" title=\"gear at ({row},{col}), ratio {ratio}\">*</span>"));
+                },
+                TypeCell::Symbol => {
+                    body.push_str(&format!("<span class=\"symbol\">{}</span>", escape_html(&line[col].to_string())));
+                },
+                TypeCell::Empty => body.push_str(&escape_html(&line[col].to_string())),
+            }
+            col += 1;
+        }
+        body.push('\n');
+    }
+
+    format!("<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Gear Ratios schematic</title>
+<style>
+body {{ background: #1e1e1e; color: #666666; }}
+pre {{ font-family: monospace; line-height: 1.2; }}
+.part {{ color: #4ec94e; }}
+.not-part {{ color: #e05252; }}
+.gear {{ color: #f0d030; font-weight: bold; }}
+.symbol {{ color: #40c0d0; }}
+span[title] {{ cursor: help; }}
+</style>
+</head>
+<body>
+<pre>
+{body}</pre>
+</body>
+</html>
+")
+}
+
+fn get_number_title(number: &NumberInfo) -> String
+{
+    let mut title = format!("{} at ({},{})", number.value, number.row, number.start_col);
+
+    if number.adjacent_symbols.is_empty() {
+        title.push_str(": no adjacent symbols");
+    }
+    else {
+        let symbols: Vec<String> = number.adjacent_symbols.iter()
+            .map(|(x, y, symbol)| format!("'{symbol}' at ({x},{y})"))
+            .collect();
+        title.push_str(&format!(": adjacent to {}", symbols.join(", ")));
+    }
+
+    title
+}
+
+fn escape_html(text: &str) -> String
+{
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROWS: [&str; 3] = ["007*..<", "...12.", ".5..&."];
+
+    fn schematic() -> AnnotatedSchematic {
+        let lines: Vec<String> = ROWS.iter().map(|row| row.to_string()).collect();
+
+        AnnotatedSchematic::from_lines(&lines).unwrap()
+    }
+
+    // Visible text of the html body with the tags removed and the entities decoded
+    fn get_html_rows(html: &str) -> Vec<String> {
+        let body = &html[html.find("<pre>\n").unwrap() + 6 .. html.find("</pre>").unwrap()];
+
+        let mut text = String::new();
+        let mut in_tag = false;
+        for character in body.chars() {
+            match character {
+                '<' => in_tag = true,
+                '>' if in_tag => in_tag = false,
+                _ if !in_tag => text.push(character),
+                _ => {},
+            }
+        }
+
+        text.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&amp;", "&")
+            .lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn html_keeps_every_cell_of_the_schematic() {
+        let html = render_html(&schematic());
+
+        assert_eq!(get_html_rows(&html), ROWS);
+        assert!(html.contains("<span class=\"part\" title=\"7 at (0,0): adjacent to '*' at (0,3)\">007</span>"));
+        assert!(html.contains("<span class=\"gear\" title=\"gear at (0,3), ratio 84\">*</span>"));
+        assert!(html.contains("<span class=\"not-part\" title=\"5 at (2,1): no adjacent symbols\">5</span>"));
+    }
+
+    #[test]
+    fn ansi_colours_every_cell() {
+        let ansi = render_ansi(&schematic());
+        let cells: Vec<usize> = ansi.lines().map(|row| row.matches(ANSI_RESET).count()).collect();
+
+        assert_eq!(cells, vec![7, 6, 6]);
+        assert!(ansi.starts_with(&format!("{ANSI_PART_NUMBER}0{ANSI_RESET}{ANSI_PART_NUMBER}0{ANSI_RESET}")));
+    }
+}
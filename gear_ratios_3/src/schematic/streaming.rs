@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use crate::schematic::adjacency::{TypeLine, get_boundary, get_coordinates_ranges, get_symbols, parse_number};
+
+#[derive(Debug, Default)]
+pub struct SchematicSums {
+    pub part_numbers_sum: u64,
+    pub gear_ratios_sum: u64,
+    pub lines: usize,
+}
+
+// Computes both parts in a single pass keeping only three lines in memory.
+// A '*' in row r can only be touched by numbers of rows r-1, r and r+1, so once
+// row r+1 is processed its gear is complete and removed from the map.
+pub fn process_schematic<R: BufRead>(reader: R) -> std::io::Result<SchematicSums>
+{
+    let mut sums = SchematicSums::default();
+    let mut map_asterix: HashMap<(usize, usize), Vec<u64>> = HashMap::new();
+
+    let mut lines = reader.lines();
+
+    let mut line_prev_chars: Vec<char> = Vec::new();
+    let mut line_curr_chars: Vec<char> = match lines.next() {
+        Some(line) => line?.chars().collect(),
+        None => return Ok(sums),
+    };
+
+    let mut line_index: usize = 0;
+    loop {
+
+        let next_line = lines.next().transpose()?;
+        let is_last_line = next_line.is_none();
+        let line_next_chars: Vec<char> = next_line.map(|line| line.chars().collect()).unwrap_or_default();
+
+        let type_line = if line_index == 0 {
+            TypeLine::First
+        }
+        else if is_last_line {
+            TypeLine::Last
+        }
+        else {
+            TypeLine::Normal
+        };
+
+        process_line(line_index, &type_line, &mut sums, &mut map_asterix,
+            &line_prev_chars, &line_curr_chars, &line_next_chars)?;
+
+        // Gears above the current line will not receive any other number
+        let complete_gears: Vec<(usize, usize)> = map_asterix.keys()
+            .filter(|(row, _)| *row < line_index || is_last_line)
+            .copied()
+            .collect();
+
+        for coords in complete_gears {
+            let values = map_asterix.remove(&coords).unwrap_or_default();
+            if values.len() == 2 {
+                let gear_ratio = values[0].checked_mul(values[1]).ok_or_else(|| std::io::Error::other(
+                    format!("The gear ratio at ({},{}) does not fit in a u64 :(", coords.0, coords.1)))?;
+                sums.gear_ratios_sum = checked_add_sum(sums.gear_ratios_sum, gear_ratio, "gear ratios")?;
+            }
+        }
+
+        if is_last_line {
+            break;
+        }
+
+        line_prev_chars = std::mem::replace(&mut line_curr_chars, line_next_chars);
+        line_index += 1;
+    }
+
+    sums.lines = line_index + 1;
+
+    Ok(sums)
+}
+
+fn process_line(line_index: usize, type_line: &TypeLine, sums: &mut SchematicSums,
+    map_asterix: &mut HashMap<(usize, usize), Vec<u64>>,
+    line_prev_chars: &[char], line_curr_chars: &[char], line_next_chars: &[char]) -> std::io::Result<()>
+{
+    let mut pos = 0;
+
+    while pos < line_curr_chars.len() {
+
+        if line_curr_chars[pos].is_ascii_digit() {
+            let (pos_start, pos_end) = get_boundary(line_curr_chars, pos);
+            let ranges = get_coordinates_ranges((pos_start, pos_end), type_line,
+                (line_prev_chars.len(), line_curr_chars.len(), line_next_chars.len()));
+            pos = pos_end;
+
+            let symbols = get_symbols(line_index, &ranges, line_prev_chars,
+                line_curr_chars, line_next_chars);
+
+            if !symbols.is_empty() {
+                let number = parse_number(line_curr_chars, (pos_start, pos_end), line_index)?;
+
+                sums.part_numbers_sum = checked_add_sum(sums.part_numbers_sum, number, "part numbers")?;
+
+                for (x, y, symbol) in symbols {
+                    if symbol == '*' {
+                        map_asterix.entry((x, y)).or_default().push(number);
+                    }
+                }
+            }
+        }
+        pos += 1;
+    }
+
+    Ok(())
+}
+
+pub fn checked_add_sum(sum: u64, value: u64, name: &str) -> std::io::Result<u64>
+{
+    sum.checked_add(value).ok_or_else(|| std::io::Error::other(format!("The sum of the {name} does not fit in a u64 :(")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n\
+        .....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..\n";
+
+    #[test]
+    fn example_sums() {
+        let sums = process_schematic(EXAMPLE.as_bytes()).unwrap();
+
+        assert_eq!((sums.part_numbers_sum, sums.gear_ratios_sum, sums.lines), (4361, 467835, 10));
+    }
+
+    #[test]
+    fn ragged_lines_give_the_same_sums() {
+        let ragged = EXAMPLE.replace("..592.....", "..592");
+        let sums = process_schematic(ragged.as_bytes()).unwrap();
+
+        assert_eq!((sums.part_numbers_sum, sums.gear_ratios_sum), (4361, 467835));
+    }
+
+    #[test]
+    fn numbers_too_big_for_a_u64_are_an_error() {
+        let schematic = "12345678901234567890123#\n";
+
+        assert!(process_schematic(schematic.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn gear_ratio_overflow_is_an_error() {
+        let schematic = "4294967296*4294967296\n";
+        let err = process_schematic(schematic.as_bytes()).expect_err("2^64 does not fit in a u64");
+
+        assert!(err.to_string().contains("(0,10)"));
+    }
+}
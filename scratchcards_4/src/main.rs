@@ -62,8 +62,16 @@
 
 use std::fs::File;
 use std::io::{BufReader, BufRead};
-use std::str::FromStr;
-use std::collections::VecDeque;
+
+use crate::scratchcards::{
+    benchmark::run_matching_benchmark,
+    card::{Card, MatchSemantics, check_card_id, check_cards_ids, get_total_points},
+    cascade::{cascade_to_dot, cascade_to_trace, get_cascade},
+    copies::{CardRule, get_total_copies, propagate_copies},
+    error::CardError,
+    report::get_cards_report};
+
+pub mod scratchcards;
 
 #[derive(Debug)]
 enum TypeRun {
@@ -71,41 +79,104 @@ enum TypeRun {
     SecondPart
 }
 
-struct Card {
-    winning_numbers: Vec<u32>,
-    selected_numbers: Vec<u32>,
+fn main() -> std::io::Result<()>
+{
+    let args: Vec<String> = std::env::args().collect();
+
+    // Numbers repeated inside a list are rejected unless asked otherwise
+    let semantics = if args.iter().any(|arg| arg == "--multiset") {
+        MatchSemantics::Multiset
+    }
+    else {
+        MatchSemantics::Set
+    };
+
+    match args.get(1).map(|arg| arg.as_str()) {
+        // Matches, points and final copies of every card
+        Some("report") => report(semantics)?,
+        // Which card won copies of which later cards, step by step or as a graph
+        Some("cascade") => cascade(semantics, false)?,
+        Some("cascade-dot") => cascade(semantics, true)?,
+        // Compare sort-merge against bitset matching on a generated deck
+        Some("bench") => {
+            let cards = match args.get(2) {
+                Some(cards) => cards.parse::<usize>().map_err(std::io::Error::other)?,
+                None => 1_000_000,
+            };
+
+            let result = run_matching_benchmark(cards).map_err(std::io::Error::other)?;
+            println!("Cards: {}, total matches: {}", result.cards, result.total_matches);
+            println!("Sort-merge: {:?}", result.sort_merge_time);
+            println!("Bitset: {:?}", result.mask_time);
+        },
+        _ => {
+            algorithm(TypeRun::FirstPart, semantics)?;
+            algorithm(TypeRun::SecondPart, semantics)?;
+        },
+    }
+
+    Ok(()) 
 }
 
-impl std::str::FromStr for Card {
-    type Err = std::num::ParseIntError;
+fn parse_card(line: &str, line_index: usize, semantics: MatchSemantics) -> std::io::Result<Card>
+{
+    Card::parse(line, semantics)
+        .map_err(|err| std::io::Error::other(format!("line {}: {err}", line_index + 1)))
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let numbers_string: Vec<&str> = s.split('|').collect();
+fn read_cards(semantics: MatchSemantics) -> std::io::Result<Vec<Card>>
+{
+    let reader = BufReader::new(File::open("data/input.txt")?);
+    let mut cards: Vec<Card> = Vec::new();
 
-        let wining_numbers: Vec<u32> = numbers_string[0].split_whitespace()
-            .map(|num| num.parse::<u32>())
-            .collect::<Result<Vec<_>, _>>()?;
-        
-        let selected_numbers: Vec<u32> = numbers_string[1].split_whitespace()
-            .map(|num| num.parse::<u32>())
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(Card {
-            winning_numbers: wining_numbers,
-            selected_numbers: selected_numbers,
-        })
+    for (i, line) in reader.lines().enumerate() {
+        cards.push(parse_card(&line?, i, semantics)?);
     }
+
+    Ok(cards)
 }
 
-fn main() -> std::io::Result<()>
+fn cascade(semantics: MatchSemantics, as_dot: bool) -> std::io::Result<()>
 {
-    algorithm(TypeRun::FirstPart)?;
-    algorithm(TypeRun::SecondPart)?;
+    let cards = read_cards(semantics)?;
+    check_cards_ids(&cards).map_err(std::io::Error::other)?;
 
-    Ok(()) 
+    let card_rules: Vec<CardRule> = cards.iter()
+        .map(|card| CardRule::next_cards(card.id, card.get_number_match_numbers()))
+        .collect();
+    let steps = get_cascade(&card_rules).map_err(std::io::Error::other)?;
+
+    if as_dot {
+        print!("{}", cascade_to_dot(&steps));
+    }
+    else {
+        let copies: Vec<u64> = steps.iter().map(|step| step.instances).collect();
+        print!("{}", cascade_to_trace(&steps));
+        println!("In total there are {} scratchcards", get_total_copies(&copies).map_err(std::io::Error::other)?);
+    }
+
+    Ok(())
+}
+
+fn report(semantics: MatchSemantics) -> std::io::Result<()>
+{
+    let cards_report = get_cards_report(&read_cards(semantics)?).map_err(std::io::Error::other)?;
+
+    println!("{:>6} {:>8} {:>8} {:>10}", "Card", "Matches", "Points", "Copies");
+    for card_report in &cards_report {
+        println!("{:>6} {:>8} {:>8} {:>10}", card_report.id, card_report.match_numbers,
+            card_report.points, card_report.copies);
+    }
+
+    let points: Vec<u64> = cards_report.iter().map(|card_report| card_report.points).collect();
+    println!("Total points: {}", get_total_points(&points).map_err(std::io::Error::other)?);
+    let copies: Vec<u64> = cards_report.iter().map(|card_report| card_report.copies).collect();
+    println!("Total scratchcards: {}", get_total_copies(&copies).map_err(std::io::Error::other)?);
+
+    Ok(())
 }
 
-fn algorithm (type_run: TypeRun) -> std::io::Result<()> 
+fn algorithm (type_run: TypeRun, semantics: MatchSemantics) -> std::io::Result<()> 
 {
     // Open the file for reading
     let file: File = File::open("data/input.txt")?;
@@ -114,100 +185,40 @@ fn algorithm (type_run: TypeRun) -> std::io::Result<()>
     let reader = BufReader::new(file);
 
 
-    let mut total_sum: u32 = 0;
+    let mut total_sum: u64 = 0;
 
-    let mut scratchcards_q: VecDeque<u32> = VecDeque::new();
+    let mut card_rules: Vec<CardRule> = Vec::new(); // only for part 2
+    let mut previous_id: Option<u32> = None;
 
-    for line in reader.lines() {
+    for (i, line) in reader.lines().enumerate() {
 
         let line_string =  line?;
-        let card_info_s: Vec<&str> = line_string.split(':').collect();
 
-        let mut card_info : Card;
-        
-        match Card::from_str(card_info_s[1]) {
-            Ok(c) => card_info = c,
-            Err(_) => {
-                return Err(std::io::Error::new(std::io::ErrorKind::Other, 
-                    "Could not convert string into card :(")); 
-            },
-        }
+        let card_info = parse_card(&line_string, i, semantics)?;
 
-        card_info.winning_numbers.sort();
-        card_info.selected_numbers.sort();
+        check_card_id(previous_id, card_info.id)
+            .map_err(|err| std::io::Error::other(format!("line {}: {err}", i + 1)))?;
+        previous_id = Some(card_info.id);
 
-        let match_numbers = get_number_match_numbers(&card_info);
+        let match_numbers = card_info.get_number_match_numbers();
         
         if matches!(type_run, TypeRun::FirstPart){
-            total_sum += if match_numbers > 0 {2_u32.pow(match_numbers-1)} else {0};
+            let points = Card::get_points(card_info.id, match_numbers).map_err(std::io::Error::other)?;
+            total_sum = total_sum.checked_add(points)
+                .ok_or_else(|| std::io::Error::other(CardError::TotalPointsOverflow))?;
         }
         else {
-
-            if scratchcards_q.is_empty(){
-
-                // This part is in case the are not previous copies won from the current card
-                for _ in 0 .. match_numbers {
-                    scratchcards_q.push_back(1);
-                }
-
-                total_sum += 1;
-            }
-            else {
-                // This part is in case the are previous copies won from the current card
-
-                // Read the previous won card and add (+1) the default one you have
-                let num_scratchcards_current_card:u32 = scratchcards_q.get(0).unwrap() + 1;
-
-                // Remove the count of card won from the current card
-                scratchcards_q.pop_front();
-
-                total_sum += num_scratchcards_current_card;
-
-                // Add the the accumulative won cards +1 to the next cards equal to the match numbers
-                for i in 0 .. match_numbers{
-
-                    if i < scratchcards_q.len().try_into().unwrap() {
-                        if let Some(elem) = scratchcards_q.get_mut(i.try_into().unwrap()) {
-                            *elem += num_scratchcards_current_card;
-                        }
-                    }
-                    else {
-                        scratchcards_q.push_back(num_scratchcards_current_card);
-                    }
-
-                }
-            }
+            card_rules.push(CardRule::next_cards(card_info.id, match_numbers));
         }
-
     }
 
+    if matches!(type_run, TypeRun::SecondPart){
+        // Every original card plus all the copies won in cascade
+        let copies: Vec<u64> = propagate_copies(&card_rules).map_err(std::io::Error::other)?;
+        total_sum = get_total_copies(&copies).map_err(std::io::Error::other)?;
+    }
 
     println!("The total sum for the quest for the {:?} is: {total_sum}", type_run);
 
     Ok(())
 }
-
-fn get_number_match_numbers(card_info: &Card) -> u32
-{
-    let mut index_winning: usize = 0;
-    let mut index_selected: usize = 0;
-
-    let mut match_numbers: u32 = 0;
-
-    while index_winning < card_info.winning_numbers.len() && index_selected < card_info.selected_numbers.len() {
-
-        if card_info.winning_numbers[index_winning] == card_info.selected_numbers[index_selected]{
-            match_numbers += 1;
-            index_selected += 1;
-            index_winning += 1;
-        }
-        else if card_info.winning_numbers[index_winning] < card_info.selected_numbers[index_selected] {
-            index_winning += 1;
-        }
-        else{
-            index_selected +=1;
-        }
-    }
-    
-    match_numbers
-}
@@ -0,0 +1,8 @@
+pub mod benchmark;
+pub mod card;
+pub mod cascade;
+pub mod copies;
+pub mod error;
+pub mod generator;
+pub mod matching;
+pub mod report;
@@ -0,0 +1,65 @@
+use std::time::{Duration, Instant};
+
+use crate::scratchcards::generator::Generator;
+use crate::scratchcards::matching::{get_number_match_mask, get_number_match_sorted, get_numbers_mask};
+
+const WINNING_NUMBERS: usize = 10;
+const SELECTED_NUMBERS: usize = 25;
+const MAX_NUMBER: u64 = 100;
+
+pub struct BenchmarkResult {
+    pub cards: usize,
+    pub sort_merge_time: Duration,
+    pub mask_time: Duration,
+    pub total_matches: u64,
+}
+
+fn next_numbers(generator: &mut Generator, amount: usize) -> Vec<u32>
+{
+    (0 .. amount).map(|_| generator.next_below(MAX_NUMBER) as u32).collect()
+}
+
+// Deck of cards shaped like the puzzle input, numbers are unsorted as in the file
+pub fn generate_deck(cards: usize, seed: u64) -> Vec<(Vec<u32>, Vec<u32>)>
+{
+    let mut generator = Generator::new(seed);
+
+    (0 .. cards)
+        .map(|_| (next_numbers(&mut generator, WINNING_NUMBERS), next_numbers(&mut generator, SELECTED_NUMBERS)))
+        .collect()
+}
+
+// Times both ways of counting matches over the same deck, including the
+// preparation each one needs (sorting or building the masks)
+pub fn run_matching_benchmark(cards: usize) -> Result<BenchmarkResult, String>
+{
+    let deck = generate_deck(cards, 0x5c7a_7c4d);
+
+    let mut sorted_deck = deck.clone();
+    let start = Instant::now();
+    let mut sort_merge_matches: u64 = 0;
+    for (winning_numbers, selected_numbers) in &mut sorted_deck {
+        winning_numbers.sort_unstable();
+        winning_numbers.dedup();
+        selected_numbers.sort_unstable();
+        selected_numbers.dedup();
+        sort_merge_matches += get_number_match_sorted(winning_numbers, selected_numbers) as u64;
+    }
+    let sort_merge_time = start.elapsed();
+
+    let start = Instant::now();
+    let mut mask_matches: u64 = 0;
+    for (winning_numbers, selected_numbers) in &deck {
+        if let (Some(winning_mask), Some(selected_mask)) =
+            (get_numbers_mask(winning_numbers), get_numbers_mask(selected_numbers)) {
+            mask_matches += get_number_match_mask(winning_mask, selected_mask) as u64;
+        }
+    }
+    let mask_time = start.elapsed();
+
+    if sort_merge_matches != mask_matches {
+        return Err(format!("sort-merge found {sort_merge_matches} matches but mask found {mask_matches}"));
+    }
+
+    Ok(BenchmarkResult { cards, sort_merge_time, mask_time, total_matches: mask_matches })
+}
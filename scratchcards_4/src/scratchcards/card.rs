@@ -0,0 +1,281 @@
+use std::str::FromStr;
+
+use crate::scratchcards::error::CardError;
+use crate::scratchcards::matching::{get_number_match_mask, get_number_match_sorted, get_numbers_mask};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchSemantics {
+    // Every number may appear only once per list, repeated numbers are an error
+    Set,
+    // Repeated numbers are allowed and a number matches min(times winning, times selected)
+    Multiset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumbersSide {
+    Winning,
+    Selected,
+}
+
+pub struct Card {
+    pub id: u32,
+    pub winning_numbers: Vec<u32>,
+    pub selected_numbers: Vec<u32>,
+    // Winning and selected masks, only when every number is below MASK_LIMIT
+    // and appears once, otherwise the sort-merge is used
+    pub numbers_masks: Option<(u128, u128)>,
+}
+
+impl FromStr for Card {
+    type Err = CardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Card::parse(s, MatchSemantics::Set)
+    }
+}
+
+impl Card {
+
+    // Parses a full line like "Card 1: 41 48 83 | 83 86  6", numbers are sorted
+    // only when they do not fit in the masks and the sort-merge is needed
+    pub fn parse(s: &str, semantics: MatchSemantics) -> Result<Self, CardError> {
+
+        let Some((id_str, numbers_str)) = s.split_once(':') else {
+            return Err(CardError::MissingId);
+        };
+
+        let id: u32 = match id_str.trim().strip_prefix("Card") {
+            Some(id_str) => id_str.trim().parse::<u32>()
+                .map_err(|_| CardError::InvalidId(id_str.trim().to_string()))?,
+            None => return Err(CardError::MissingId),
+        };
+
+        let numbers_string: Vec<&str> = numbers_str.split('|').collect();
+
+        match numbers_string.len() {
+            1 => return Err(CardError::MissingSeparator(id)),
+            2 => {},
+            _ => return Err(CardError::TooManySeparators(id)),
+        }
+
+        let mut wining_numbers = Self::parse_numbers(id, NumbersSide::Winning, numbers_string[0])?;
+        let mut selected_numbers = Self::parse_numbers(id, NumbersSide::Selected, numbers_string[1])?;
+
+        if semantics == MatchSemantics::Set {
+            for (side, numbers) in [(NumbersSide::Winning, &wining_numbers), (NumbersSide::Selected, &selected_numbers)] {
+                if let Some(number) = get_duplicate_number(numbers) {
+                    return Err(CardError::DuplicateNumber { id, side, number });
+                }
+            }
+        }
+
+        // A mask loses repeated numbers, so it is only valid if it has one bit per number
+        let numbers_masks = get_numbers_mask(&wining_numbers)
+            .zip(get_numbers_mask(&selected_numbers))
+            .filter(|(winning_mask, selected_mask)|
+                winning_mask.count_ones() as usize == wining_numbers.len() &&
+                selected_mask.count_ones() as usize == selected_numbers.len());
+
+        if numbers_masks.is_none() {
+            wining_numbers.sort();
+            selected_numbers.sort();
+        }
+
+        Ok(Card {
+            id,
+            winning_numbers: wining_numbers,
+            selected_numbers,
+            numbers_masks,
+        })
+    }
+
+    fn parse_numbers(id: u32, side: NumbersSide, numbers_str: &str) -> Result<Vec<u32>, CardError> {
+
+        let numbers: Vec<u32> = numbers_str.split_whitespace()
+            .map(|num| num.parse::<u32>()
+                .map_err(|_| CardError::InvalidNumber { id, side, token: num.to_string() }))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if numbers.is_empty() {
+            return Err(CardError::EmptyNumbers { id, side });
+        }
+
+        Ok(numbers)
+    }
+
+    pub fn get_number_match_numbers(&self) -> u32
+    {
+        match self.numbers_masks {
+            Some((winning_mask, selected_mask)) => get_number_match_mask(winning_mask, selected_mask),
+            None => get_number_match_sorted(&self.winning_numbers, &self.selected_numbers),
+        }
+    }
+
+    // 2^(matches - 1), cards with more than 64 matches are worth more than a u64
+    pub fn get_points(id: u32, match_numbers: u32) -> Result<u64, CardError>
+    {
+        if match_numbers == 0 {
+            return Ok(0);
+        }
+
+        1_u64.checked_shl(match_numbers - 1).ok_or(CardError::PointsOverflow(id))
+    }
+}
+
+pub fn get_total_points(points: &[u64]) -> Result<u64, CardError>
+{
+    points.iter().try_fold(0_u64, |total, points| total.checked_add(*points))
+        .ok_or(CardError::TotalPointsOverflow)
+}
+
+// Cards must come as 1, 2, 3 ... without gaps or repeated ids
+pub fn check_card_id(previous_id: Option<u32>, id: u32) -> Result<(), CardError>
+{
+    let expected = match previous_id {
+        Some(prev) => prev.checked_add(1).ok_or(CardError::IdOverflow(id))?,
+        None => 1,
+    };
+
+    if id < expected {
+        Err(CardError::DuplicateId(id))
+    }
+    else if id > expected {
+        Err(CardError::GapInIds { expected, found: id })
+    }
+    else {
+        Ok(())
+    }
+}
+
+pub fn check_cards_ids(cards: &[Card]) -> Result<(), CardError>
+{
+    let mut previous_id: Option<u32> = None;
+    for card in cards {
+        check_card_id(previous_id, card.id)?;
+        previous_id = Some(card.id);
+    }
+
+    Ok(())
+}
+
+fn get_duplicate_number(numbers: &[u32]) -> Option<u32>
+{
+    let mut sorted_numbers = numbers.to_vec();
+    sorted_numbers.sort_unstable();
+
+    sorted_numbers.windows(2).find(|pair| pair[0] == pair[1]).map(|pair| pair[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_double_with_every_match() {
+        assert_eq!(Card::get_points(1, 0).unwrap(), 0);
+        assert_eq!(Card::get_points(1, 1).unwrap(), 1);
+        assert_eq!(Card::get_points(1, 4).unwrap(), 8);
+        assert_eq!(Card::get_points(1, 64).unwrap(), 1 << 63);
+    }
+
+    #[test]
+    fn points_overflow_is_an_error() {
+        assert!(matches!(Card::get_points(7, 65), Err(CardError::PointsOverflow(7))));
+        assert!(matches!(Card::get_points(7, 128), Err(CardError::PointsOverflow(7))));
+        assert!(matches!(get_total_points(&[1 << 63, 1 << 63]), Err(CardError::TotalPointsOverflow)));
+        assert_eq!(get_total_points(&[8, 2, 2, 1, 0, 0]).unwrap(), 13);
+    }
+
+    fn parse_err(line: &str) -> CardError {
+        match Card::parse(line, MatchSemantics::Set) {
+            Ok(_) => panic!("'{line}' should not parse"),
+            Err(err) => err,
+        }
+    }
+
+    fn match_numbers(line: &str, semantics: MatchSemantics) -> u32 {
+        Card::parse(line, semantics).unwrap().get_number_match_numbers()
+    }
+
+    #[test]
+    fn lines_without_card_id() {
+        assert!(matches!(parse_err("Card 1 41 48 | 83 86"), CardError::MissingId));
+        assert!(matches!(parse_err("1: 41 48 | 83 86"), CardError::MissingId));
+        assert!(matches!(parse_err(""), CardError::MissingId));
+    }
+
+    #[test]
+    fn invalid_card_ids() {
+        assert!(matches!(parse_err("Card one: 41 | 83"), CardError::InvalidId(id) if id == "one"));
+        assert!(matches!(parse_err("Card -1: 41 | 83"), CardError::InvalidId(id) if id == "-1"));
+        assert!(matches!(parse_err("Card : 41 | 83"), CardError::InvalidId(id) if id.is_empty()));
+    }
+
+    #[test]
+    fn separators() {
+        assert!(matches!(parse_err("Card 3: 41 48 83 86"), CardError::MissingSeparator(3)));
+        assert!(matches!(parse_err("Card 3: 41 | 48 | 83"), CardError::TooManySeparators(3)));
+    }
+
+    #[test]
+    fn empty_number_lists() {
+        assert!(matches!(parse_err("Card 2:   | 83 86"),
+            CardError::EmptyNumbers { id: 2, side: NumbersSide::Winning }));
+        assert!(matches!(parse_err("Card 2: 41 48 |  "),
+            CardError::EmptyNumbers { id: 2, side: NumbersSide::Selected }));
+    }
+
+    #[test]
+    fn invalid_numbers() {
+        assert!(matches!(parse_err("Card 4: 41 4x8 | 83"),
+            CardError::InvalidNumber { id: 4, side: NumbersSide::Winning, token } if token == "4x8"));
+        assert!(matches!(parse_err("Card 4: 41 | 83 -6"),
+            CardError::InvalidNumber { id: 4, side: NumbersSide::Selected, token } if token == "-6"));
+        assert!(matches!(parse_err("Card 4: 41 | 99999999999"),
+            CardError::InvalidNumber { id: 4, side: NumbersSide::Selected, .. }));
+    }
+
+    #[test]
+    fn repeated_numbers_are_rejected_only_as_sets() {
+        assert!(matches!(parse_err("Card 5: 41 48 41 | 83"),
+            CardError::DuplicateNumber { id: 5, side: NumbersSide::Winning, number: 41 }));
+        assert!(matches!(parse_err("Card 5: 41 | 200 83 200"),
+            CardError::DuplicateNumber { id: 5, side: NumbersSide::Selected, number: 200 }));
+
+        assert!(Card::parse("Card 5: 41 48 41 | 83", MatchSemantics::Multiset).is_ok());
+    }
+
+    #[test]
+    fn set_match_counts() {
+        let line = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
+        assert_eq!(match_numbers(line, MatchSemantics::Set), 4);
+        assert_eq!(match_numbers(line, MatchSemantics::Multiset), 4);
+
+        // Numbers past the mask go through the sort-merge
+        let line = "Card 1: 41 480 83 | 83 480 6 1000";
+        assert!(Card::parse(line, MatchSemantics::Set).unwrap().numbers_masks.is_none());
+        assert_eq!(match_numbers(line, MatchSemantics::Set), 2);
+    }
+
+    #[test]
+    fn multiset_match_counts() {
+        // 5 matches twice (min of 2 and 3), 7 once, 9 is not winning
+        assert_eq!(match_numbers("Card 1: 5 5 7 | 5 5 5 7 9", MatchSemantics::Multiset), 3);
+        assert_eq!(match_numbers("Card 1: 5 5 5 | 5", MatchSemantics::Multiset), 1);
+        assert_eq!(match_numbers("Card 1: 300 300 7 | 300 300 300", MatchSemantics::Multiset), 2);
+    }
+
+    #[test]
+    fn card_ids_in_order() {
+        let cards: Vec<Card> = ["Card 1: 1 | 1", "Card 2: 1 | 2", "Card 2: 1 | 3"].iter()
+            .map(|line| line.parse::<Card>().unwrap())
+            .collect();
+
+        assert!(check_cards_ids(&cards[.. 2]).is_ok());
+        assert!(matches!(check_cards_ids(&cards), Err(CardError::DuplicateId(2))));
+        assert!(matches!(check_cards_ids(&cards[1 ..]), Err(CardError::GapInIds { expected: 1, found: 2 })));
+
+        assert!(check_card_id(Some(u32::MAX - 1), u32::MAX).is_ok());
+        assert!(matches!(check_card_id(Some(u32::MAX), u32::MAX), Err(CardError::IdOverflow(u32::MAX))));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::scratchcards::copies::{CardRule, WinRule, propagate_copies};
+use crate::scratchcards::error::CardError;
+
+pub struct CascadeStep {
+    pub id: u32,
+    pub instances: u64,
+    // Target card id and the copies of it won by all the instances of this card
+    pub won: Vec<(u32, u64)>,
+}
+
+// Walks the cards in order telling which later cards each one copied and how many times
+pub fn get_cascade(rules: &[CardRule]) -> Result<Vec<CascadeStep>, CardError>
+{
+    let counts: Vec<u64> = propagate_copies(rules)?;
+    let last_id = rules.last().map_or(0, |rule| rule.id);
+
+    let mut steps: Vec<CascadeStep> = Vec::new();
+
+    for (rule, instances) in rules.iter().zip(counts) {
+
+        let targets: Vec<u32> = match &rule.win {
+            WinRule::NextCards(n) => match rule.id.checked_add(1) {
+                Some(next_id) => {
+                    let n = u32::try_from(*n).unwrap_or(u32::MAX);
+                    (next_id ..=last_id.min(rule.id.saturating_add(n))).collect()
+                },
+                None => Vec::new(),
+            },
+            WinRule::Cards(targets) => targets.clone(),
+        };
+
+        // Already checked for overflow by propagate_copies
+        let copies = instances * rule.multiplier;
+
+        steps.push(CascadeStep {
+            id: rule.id,
+            instances,
+            won: targets.into_iter().map(|target| (target, copies)).collect(),
+        });
+    }
+
+    Ok(steps)
+}
+
+pub fn cascade_to_trace(steps: &[CascadeStep]) -> String
+{
+    let mut result = String::new();
+
+    for step in steps {
+        result.push_str(&format!("Card {}: {} (1 original + {})", step.id,
+            plural(step.instances, "instance"), plural(step.instances - 1, "copy")));
+
+        if step.won.is_empty() {
+            result.push_str(" wins no cards\n");
+            continue;
+        }
+
+        let targets: Vec<String> = step.won.iter().map(|(target, _)| target.to_string()).collect();
+        let copies = plural(step.won[0].1, "copy");
+
+        if targets.len() == 1 {
+            result.push_str(&format!(" wins {copies} of card {}\n", targets[0]));
+        }
+        else {
+            result.push_str(&format!(" wins {copies} each of cards {}\n", targets.join(", ")));
+        }
+    }
+
+    result
+}
+
+pub fn cascade_to_dot(steps: &[CascadeStep]) -> String
+{
+    let mut result = String::from("digraph cascade {\n    rankdir=LR;\n");
+
+    for step in steps {
+        result.push_str(&format!("    c{} [label=\"Card {}\\n{}\", shape=box];\n",
+            step.id, step.id, plural(step.instances, "instance")));
+    }
+
+    for step in steps {
+        for (target, copies) in &step.won {
+            result.push_str(&format!("    c{} -> c{target} [label=\"{copies}\"];\n", step.id));
+        }
+    }
+
+    result.push_str("}\n");
+    result
+}
+
+fn plural(amount: u64, word: &str) -> String
+{
+    match (amount, word) {
+        (1, _) => format!("1 {word}"),
+        (_, "copy") => format!("{amount} copies"),
+        _ => format!("{amount} {word}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Matches of the cards of the puzzle example
+    fn example_rules() -> Vec<CardRule> {
+        [4, 2, 2, 1, 0, 0].iter().enumerate()
+            .map(|(i, match_numbers)| CardRule::next_cards(i as u32 + 1, *match_numbers))
+            .collect()
+    }
+
+    #[test]
+    fn example_steps() {
+        let steps = get_cascade(&example_rules()).unwrap();
+
+        let instances: Vec<(u32, u64)> = steps.iter().map(|step| (step.id, step.instances)).collect();
+        assert_eq!(instances, vec![(1, 1), (2, 2), (3, 4), (4, 8), (5, 14), (6, 1)]);
+
+        let won: Vec<&[(u32, u64)]> = steps.iter().map(|step| step.won.as_slice()).collect();
+        assert_eq!(won, vec![
+            &[(2, 1), (3, 1), (4, 1), (5, 1)][..],
+            &[(3, 2), (4, 2)],
+            &[(4, 4), (5, 4)],
+            &[(5, 8)],
+            &[],
+            &[],
+        ]);
+    }
+
+    #[test]
+    fn example_trace() {
+        let steps = get_cascade(&example_rules()).unwrap();
+
+        assert_eq!(cascade_to_trace(&steps), "\
+Card 1: 1 instance (1 original + 0 copies) wins 1 copy each of cards 2, 3, 4, 5
+Card 2: 2 instances (1 original + 1 copy) wins 2 copies each of cards 3, 4
+Card 3: 4 instances (1 original + 3 copies) wins 4 copies each of cards 4, 5
+Card 4: 8 instances (1 original + 7 copies) wins 8 copies of card 5
+Card 5: 14 instances (1 original + 13 copies) wins no cards
+Card 6: 1 instance (1 original + 0 copies) wins no cards
+");
+    }
+
+    #[test]
+    fn example_dot() {
+        let rules: Vec<CardRule> = (4 ..= 6).map(|id| CardRule::next_cards(id, 1)).collect();
+        let steps = get_cascade(&rules).unwrap();
+
+        assert_eq!(cascade_to_dot(&steps), "\
+digraph cascade {
+    rankdir=LR;
+    c4 [label=\"Card 4\\n1 instance\", shape=box];
+    c5 [label=\"Card 5\\n2 instances\", shape=box];
+    c6 [label=\"Card 6\\n3 instances\", shape=box];
+    c4 -> c5 [label=\"1\"];
+    c5 -> c6 [label=\"2\"];
+}
+");
+    }
+
+    #[test]
+    fn last_possible_id_wins_no_cards() {
+        let rules = vec![CardRule::next_cards(u32::MAX - 1, 5), CardRule::next_cards(u32::MAX, 5)];
+        let steps = get_cascade(&rules).unwrap();
+
+        assert_eq!(steps[0].won, vec![(u32::MAX, 1)]);
+        assert!(steps[1].won.is_empty());
+        assert_eq!(steps[1].instances, 2);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::scratchcards::error::CardError;
+
+pub enum WinRule {
+    // One copy of each of the next N cards, stopping at the end of the table
+    NextCards(usize),
+    // One copy of each of the given cards, which must come after the winning card
+    Cards(Vec<u32>),
+}
+
+pub struct CardRule {
+    pub id: u32,
+    pub win: WinRule,
+    // Copies won of every target per instance of the card
+    pub multiplier: u64,
+}
+
+impl CardRule {
+
+    pub fn next_cards(id: u32, match_numbers: u32) -> Self {
+        CardRule { id, win: WinRule::NextCards(match_numbers as usize), multiplier: 1 }
+    }
+}
+
+pub trait CopyCount: Copy {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn from_u64(value: u64) -> Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_copy_count {
+    ($($t:ty),*) => {
+        $(
+            impl CopyCount for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn from_u64(value: u64) -> Self { value as $t }
+                fn checked_add(self, other: Self) -> Option<Self> { <$t>::checked_add(self, other) }
+                fn checked_sub(self, other: Self) -> Option<Self> { <$t>::checked_sub(self, other) }
+                fn checked_mul(self, other: Self) -> Option<Self> { <$t>::checked_mul(self, other) }
+            }
+        )*
+    };
+}
+
+impl_copy_count!(u64, u128);
+
+// Final number of instances of every card, in the same order as the rules.
+// Copies won over a range of cards are added with a difference array: the amount
+// is added where the range starts and removed after it ends, so the work is
+// O(cards + specific targets) no matter how long the ranges are.
+pub fn propagate_copies<T: CopyCount>(rules: &[CardRule]) -> Result<Vec<T>, CardError>
+{
+    let Some(first_rule) = rules.first() else {
+        return Ok(Vec::new());
+    };
+
+    let first_id = first_rule.id;
+    let len = rules.len();
+
+    let mut range_starts: Vec<T> = vec![T::ZERO; len + 1];
+    let mut range_ends: Vec<T> = vec![T::ZERO; len + 1];
+    let mut counts: Vec<T> = Vec::with_capacity(len);
+
+    let mut running: T = T::ZERO;
+
+    for (i, rule) in rules.iter().enumerate() {
+
+        let overflow = || CardError::CopiesOverflow(rule.id);
+
+        // Ranges ending here were already added, so removing them first can't underflow
+        // and keeps the running value from overflowing when the real count fits
+        running = running.checked_sub(range_ends[i]).ok_or_else(overflow)?;
+        running = running.checked_add(range_starts[i]).ok_or_else(overflow)?;
+
+        let count = running.checked_add(T::ONE).ok_or_else(overflow)?;
+        counts.push(count);
+
+        let won = count.checked_mul(T::from_u64(rule.multiplier)).ok_or_else(overflow)?;
+
+        match &rule.win {
+            WinRule::NextCards(n) => {
+                let last = (i + n).min(len - 1);
+
+                if last > i {
+                    range_starts[i + 1] = range_starts[i + 1].checked_add(won).ok_or_else(overflow)?;
+                    range_ends[last + 1] = range_ends[last + 1].checked_add(won).ok_or_else(overflow)?;
+                }
+            },
+            WinRule::Cards(targets) => {
+                for target in targets {
+                    let target_i = target.checked_sub(first_id).map(|target_i| target_i as usize);
+
+                    match target_i {
+                        Some(target_i) if target_i > i && target_i < len => {
+                            range_starts[target_i] = range_starts[target_i].checked_add(won).ok_or_else(overflow)?;
+                            range_ends[target_i + 1] = range_ends[target_i + 1].checked_add(won).ok_or_else(overflow)?;
+                        },
+                        _ => return Err(CardError::InvalidCopyTarget { id: rule.id, target: *target }),
+                    }
+                }
+            },
+        }
+    }
+
+    Ok(counts)
+}
+
+pub fn get_total_copies<T: CopyCount>(counts: &[T]) -> Result<T, CardError>
+{
+    counts.iter().try_fold(T::ZERO, |total, count| total.checked_add(*count))
+        .ok_or(CardError::TotalCopiesOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratchcards::generator::Generator;
+
+    // Processes every instance one by one, as the puzzle describes it
+    fn simulate_copies(rules: &[CardRule]) -> Vec<u64> {
+        let first_id = rules[0].id;
+        let mut counts: Vec<u64> = vec![0; rules.len()];
+        let mut pending: Vec<usize> = (0 .. rules.len()).collect();
+
+        while let Some(i) = pending.pop() {
+            counts[i] += 1;
+
+            let targets: Vec<usize> = match &rules[i].win {
+                WinRule::NextCards(n) => (i + 1 ..= (i + n).min(rules.len() - 1)).collect(),
+                WinRule::Cards(targets) => targets.iter().map(|target| (target - first_id) as usize).collect(),
+            };
+            for target in targets {
+                for _ in 0 .. rules[i].multiplier {
+                    pending.push(target);
+                }
+            }
+        }
+
+        counts
+    }
+
+    fn random_rules(generator: &mut Generator, first_id: u32) -> Vec<CardRule> {
+        let len = 1 + generator.next_below(8) as u32;
+
+        (0 .. len).map(|i| {
+            let id = first_id + i;
+            let win = if generator.next_below(2) == 0 {
+                WinRule::NextCards(generator.next_below(4) as usize)
+            }
+            else {
+                let later_cards = (len - 1 - i) as u64;
+                let amount = if later_cards == 0 {0} else {generator.next_below(4)};
+                WinRule::Cards((0 .. amount).map(|_| id + 1 + generator.next_below(later_cards) as u32).collect())
+            };
+            CardRule { id, win, multiplier: 1 + generator.next_below(2) }
+        }).collect()
+    }
+
+    #[test]
+    fn example_copies() {
+        let rules: Vec<CardRule> = [4, 2, 2, 1, 0, 0].iter().enumerate()
+            .map(|(i, match_numbers)| CardRule::next_cards(i as u32 + 1, *match_numbers))
+            .collect();
+
+        let counts: Vec<u64> = propagate_copies(&rules).unwrap();
+        assert_eq!(counts, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(get_total_copies(&counts).unwrap(), 30);
+    }
+
+    #[test]
+    fn difference_array_matches_the_simulation() {
+        let mut generator = Generator::new(0x9e37_79b9_7f4a_7c15);
+
+        for table in 0 .. 2_000 {
+            let rules = random_rules(&mut generator, 1 + table % 5);
+            let expected = simulate_copies(&rules);
+
+            let counts: Vec<u64> = propagate_copies(&rules).unwrap();
+            assert_eq!(counts, expected, "table {table}");
+
+            let counts: Vec<u128> = propagate_copies(&rules).unwrap();
+            assert!(counts.iter().zip(&expected).all(|(count, expected)| *count == *expected as u128), "table {table}");
+        }
+    }
+
+    #[test]
+    fn copies_overflowing_u64_are_an_error() {
+        // Every card wins a copy of all the later ones, so card i has 2^(i - 1) instances
+        let rules: Vec<CardRule> = (1 ..= 70).map(|id| CardRule::next_cards(id, 70)).collect();
+
+        assert!(matches!(propagate_copies::<u64>(&rules), Err(CardError::CopiesOverflow(65))));
+
+        let counts: Vec<u128> = propagate_copies(&rules).unwrap();
+        assert_eq!(counts[64], 1 << 64);
+        assert_eq!(get_total_copies(&counts).unwrap(), (1 << 70) - 1);
+    }
+
+    #[test]
+    fn multiplier_overflowing_u64_is_an_error() {
+        let rules = vec![
+            CardRule { id: 1, win: WinRule::Cards(vec![2]), multiplier: u64::MAX },
+            CardRule::next_cards(2, 0),
+        ];
+
+        assert!(matches!(propagate_copies::<u64>(&rules), Err(CardError::CopiesOverflow(2))));
+
+        let counts: Vec<u128> = propagate_copies(&rules).unwrap();
+        assert_eq!(counts, vec![1, u64::MAX as u128 + 1]);
+    }
+
+    #[test]
+    fn range_ending_before_a_big_one_starts_does_not_overflow() {
+        let rules = vec![
+            CardRule { id: 1, win: WinRule::Cards(vec![2]), multiplier: 1 << 63 },
+            CardRule { id: 2, win: WinRule::Cards(vec![3]), multiplier: 1 },
+            CardRule::next_cards(3, 0),
+        ];
+
+        let counts: Vec<u64> = propagate_copies(&rules).unwrap();
+        assert_eq!(counts, vec![1, (1 << 63) + 1, (1 << 63) + 2]);
+    }
+
+    #[test]
+    fn total_overflowing_u64_is_an_error() {
+        assert!(matches!(get_total_copies(&[u64::MAX, 1]), Err(CardError::TotalCopiesOverflow)));
+    }
+
+    #[test]
+    fn copies_of_earlier_or_missing_cards_are_an_error() {
+        for target in [1, 2, 4] {
+            let rules = vec![
+                CardRule::next_cards(1, 1),
+                CardRule { id: 2, win: WinRule::Cards(vec![target]), multiplier: 1 },
+                CardRule::next_cards(3, 0),
+            ];
+
+            assert!(matches!(propagate_copies::<u64>(&rules),
+                Err(CardError::InvalidCopyTarget { id: 2, target: found }) if found == target));
+        }
+    }
+}
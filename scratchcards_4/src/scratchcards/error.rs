@@ -0,0 +1,62 @@
+use std::fmt;
+
+use crate::scratchcards::card::NumbersSide;
+
+#[derive(Debug)]
+pub enum CardError {
+    MissingId,
+    InvalidId(String),
+    MissingSeparator(u32),
+    TooManySeparators(u32),
+    EmptyNumbers { id: u32, side: NumbersSide },
+    InvalidNumber { id: u32, side: NumbersSide, token: String },
+    DuplicateNumber { id: u32, side: NumbersSide, number: u32 },
+    DuplicateId(u32),
+    GapInIds { expected: u32, found: u32 },
+    IdOverflow(u32),
+    PointsOverflow(u32),
+    TotalPointsOverflow,
+    CopiesOverflow(u32),
+    TotalCopiesOverflow,
+    InvalidCopyTarget { id: u32, target: u32 },
+}
+
+impl fmt::Display for NumbersSide {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumbersSide::Winning => write!(f, "winning"),
+            NumbersSide::Selected => write!(f, "selected"),
+        }
+    }
+}
+
+impl fmt::Display for CardError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardError::MissingId => write!(f, "card line does not start with 'Card <id>:'"),
+            CardError::InvalidId(id) => write!(f, "'{id}' is not a valid card id"),
+            CardError::MissingSeparator(id) =>
+                write!(f, "card {id} has no '|' between winning and selected numbers"),
+            CardError::TooManySeparators(id) => write!(f, "card {id} has more than one '|'"),
+            CardError::EmptyNumbers { id, side } => write!(f, "card {id} has no {side} numbers"),
+            CardError::InvalidNumber { id, side, token } =>
+                write!(f, "card {id} has '{token}' in its {side} numbers, which is not a number"),
+            CardError::DuplicateNumber { id, side, number } =>
+                write!(f, "card {id} has {number} more than once in its {side} numbers"),
+            CardError::DuplicateId(id) => write!(f, "card {id} appears more than once"),
+            CardError::GapInIds { expected, found } =>
+                write!(f, "expected card {expected} but found card {found}"),
+            CardError::IdOverflow(id) => write!(f, "card {id} comes after the last possible card id"),
+            CardError::PointsOverflow(id) => write!(f, "points of card {id} overflow"),
+            CardError::TotalPointsOverflow => write!(f, "total number of points overflows"),
+            CardError::CopiesOverflow(id) => write!(f, "number of copies overflows at card {id}"),
+            CardError::TotalCopiesOverflow => write!(f, "total number of scratchcards overflows"),
+            CardError::InvalidCopyTarget { id, target } =>
+                write!(f, "card {id} can not win copies of card {target}, only of later cards in the table"),
+        }
+    }
+}
+
+impl std::error::Error for CardError {}
@@ -0,0 +1,22 @@
+// xorshift64 pseudo random numbers, the same seed always gives the same sequence.
+// Used to generate benchmark decks and test tables without extra dependencies.
+pub struct Generator(u64);
+
+impl Generator {
+
+    // xorshift stays at zero forever, so a zero seed is moved to one
+    pub fn new(seed: u64) -> Self {
+        Generator(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn next_below(&mut self, limit: u64) -> u64 {
+        self.next_u64() % limit
+    }
+}
@@ -0,0 +1,47 @@
+// Numbers below this value fit in a u128 mask, one bit per number
+pub const MASK_LIMIT: u32 = 128;
+
+pub fn get_numbers_mask(numbers: &[u32]) -> Option<u128>
+{
+    let mut mask: u128 = 0;
+
+    for num in numbers {
+        if *num >= MASK_LIMIT {
+            return None;
+        }
+        mask |= 1 << num;
+    }
+
+    Some(mask)
+}
+
+pub fn get_number_match_mask(winning_mask: u128, selected_mask: u128) -> u32
+{
+    (winning_mask & selected_mask).count_ones()
+}
+
+// Both lists have to be sorted
+pub fn get_number_match_sorted(winning_numbers: &[u32], selected_numbers: &[u32]) -> u32
+{
+    let mut index_winning: usize = 0;
+    let mut index_selected: usize = 0;
+
+    let mut match_numbers: u32 = 0;
+
+    while index_winning < winning_numbers.len() && index_selected < selected_numbers.len() {
+
+        if winning_numbers[index_winning] == selected_numbers[index_selected]{
+            match_numbers += 1;
+            index_selected += 1;
+            index_winning += 1;
+        }
+        else if winning_numbers[index_winning] < selected_numbers[index_selected] {
+            index_winning += 1;
+        }
+        else{
+            index_selected +=1;
+        }
+    }
+
+    match_numbers
+}
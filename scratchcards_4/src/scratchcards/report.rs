@@ -0,0 +1,60 @@
+use crate::scratchcards::card::{Card, check_cards_ids};
+use crate::scratchcards::copies::{CardRule, propagate_copies};
+use crate::scratchcards::error::CardError;
+
+#[derive(Debug)]
+pub struct CardReport {
+    pub id: u32,
+    pub match_numbers: u32,
+    pub points: u64,
+    pub copies: u64,
+}
+
+pub fn get_cards_report(cards: &[Card]) -> Result<Vec<CardReport>, CardError>
+{
+    check_cards_ids(cards)?;
+
+    let match_numbers: Vec<u32> = cards.iter().map(|card| card.get_number_match_numbers()).collect();
+
+    let rules: Vec<CardRule> = cards.iter().zip(&match_numbers)
+        .map(|(card, match_numbers)| CardRule::next_cards(card.id, *match_numbers))
+        .collect();
+    let copies: Vec<u64> = propagate_copies(&rules)?;
+
+    cards.iter().enumerate().map(|(i, card)| Ok(CardReport {
+        id: card.id,
+        match_numbers: match_numbers[i],
+        points: Card::get_points(card.id, match_numbers[i])?,
+        copies: copies[i],
+    })).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn example_report() {
+        let cards: Vec<Card> = EXAMPLE.lines().map(|line| line.parse::<Card>().unwrap()).collect();
+        let cards_report = get_cards_report(&cards).unwrap();
+
+        let rows: Vec<(u32, u32, u64, u64)> = cards_report.iter()
+            .map(|card_report| (card_report.id, card_report.match_numbers, card_report.points, card_report.copies))
+            .collect();
+        assert_eq!(rows, vec![(1, 4, 8, 1), (2, 2, 2, 2), (3, 2, 2, 4), (4, 1, 1, 8), (5, 0, 0, 14), (6, 0, 0, 1)]);
+    }
+
+    #[test]
+    fn cards_out_of_order_are_an_error() {
+        let cards: Vec<Card> = EXAMPLE.lines().rev().map(|line| line.parse::<Card>().unwrap()).collect();
+
+        assert!(matches!(get_cards_report(&cards), Err(CardError::GapInIds { expected: 1, found: 6 })));
+    }
+}
@@ -1,4 +1,7 @@
-pub mod first_part;
-pub mod second_part;
+pub mod almanac;
+pub mod benchmark;
 pub mod common;
-pub mod error;
\ No newline at end of file
+pub mod error;
+pub mod generator;
+pub mod trace;
+pub mod validation;
@@ -0,0 +1,682 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use crate::algorithms::{
+    common::{Range, SeedRangeInfo, intersect_ranges, normalize_ranges},
+    error::SeedsDBError,
+    validation::{MapLine, RangeIssue, validate_map_lines}};
+
+// One "X-to-Y map:" section of the almanac
+#[derive(Clone, Debug)]
+pub struct AlmanacMap {
+    pub source: String,
+    pub destination: String,
+    pub ranges: Vec<SeedRangeInfo>,
+}
+
+impl AlmanacMap {
+
+    // Reads the categories of a header like "seed-to-soil map:"
+    fn from_header(line: &str) -> Option<Self>
+    {
+        let categories = line.trim().strip_suffix("map:")?.trim();
+        let (source, destination) = categories.split_once("-to-")?;
+
+        if source.is_empty() || destination.is_empty() {
+            return None;
+        }
+
+        Some(AlmanacMap {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            ranges: Vec::new(),
+        })
+    }
+
+    // The ranges are sorted by source, so the only candidate is the last one starting before the value
+    pub fn find_range(&self, value: u64) -> Option<&SeedRangeInfo>
+    {
+        let index = self.ranges.partition_point(|range| range.start_source_index <= value);
+
+        index.checked_sub(1).map(|index| &self.ranges[index])
+            .filter(|range| value <= range.end_source_index)
+    }
+
+    pub fn map_value(&self, value: u64) -> u64
+    {
+        match self.find_range(value) {
+            Some(range) => {
+                let offset_destination = value - range.start_source_index;
+                range.start_destination_index + offset_destination
+            },
+            None => value,
+        }
+    }
+
+    // Splits the range in the pieces covered by each map range (or by the
+    // identity gaps between them) and converts every piece
+    pub fn map_range(&self, range: &Range) -> Vec<Range>
+    {
+        self.split_range(range).iter()
+            .map(|piece| Range::new(piece.start_destination_index,
+                piece.start_destination_index + (piece.end_source_index - piece.start_source_index)))
+            .collect()
+    }
+
+    // Pieces of the range, in order, each with the map range or identity gap converting it
+    pub fn split_range(&self, range: &Range) -> Vec<SeedRangeInfo>
+    {
+        let mut pieces: Vec<SeedRangeInfo> = Vec::new();
+
+        let mut current_num = range.start_index;
+
+        loop {
+            let temp_range_info = self.get_range_info(current_num, range.end_index);
+            let offset_destination = current_num - temp_range_info.start_source_index;
+            let last_num = range.end_index.min(temp_range_info.end_source_index);
+
+            pieces.push(SeedRangeInfo::new(current_num, last_num,
+                temp_range_info.start_destination_index + offset_destination));
+
+            if last_num == range.end_index {
+                break;
+            }
+            current_num = last_num + 1;
+        }
+
+        pieces
+    }
+
+    // Single map doing this one and then `next`, the identity pieces are left out
+    // and neighbour pieces with the same offset are joined
+    pub fn compose(&self, next: &AlmanacMap) -> Result<AlmanacMap, SeedsDBError>
+    {
+        if self.destination != next.source {
+            return Err(SeedsDBError::NoRoute { from: self.destination.clone(), to: next.source.clone() });
+        }
+
+        let mut ranges: Vec<SeedRangeInfo> = Vec::new();
+
+        for piece in self.split_range(&Range::new(0, u64::MAX)) {
+            let piece_destination = Range::new(piece.start_destination_index,
+                piece.start_destination_index + (piece.end_source_index - piece.start_source_index));
+
+            for next_piece in next.split_range(&piece_destination) {
+                let start_source_index = piece.start_source_index + (next_piece.start_source_index - piece_destination.start_index);
+                let end_source_index = start_source_index + (next_piece.end_source_index - next_piece.start_source_index);
+
+                if start_source_index == next_piece.start_destination_index {
+                    continue;
+                }
+
+                match ranges.last_mut() {
+                    Some(last) if last.end_source_index + 1 == start_source_index &&
+                        last.start_destination_index + (start_source_index - last.start_source_index) == next_piece.start_destination_index => {
+                        last.end_source_index = end_source_index;
+                    },
+                    _ => ranges.push(SeedRangeInfo::new(start_source_index, end_source_index, next_piece.start_destination_index)),
+                }
+            }
+        }
+
+        Ok(AlmanacMap {
+            source: self.source.clone(),
+            destination: next.destination.clone(),
+            ranges,
+        })
+    }
+
+    // Whole conversion as "src_start,src_end,offset" lines covering every number,
+    // identity gaps included with offset 0
+    pub fn to_csv(&self) -> String
+    {
+        let mut result = String::from("src_start,src_end,offset\n");
+
+        for piece in self.split_range(&Range::new(0, u64::MAX)) {
+            let offset = piece.start_destination_index as i128 - piece.start_source_index as i128;
+            result.push_str(&format!("{},{},{offset}\n", piece.start_source_index, piece.end_source_index));
+        }
+
+        result
+    }
+
+    // Every source number converted into a number of the range: the pieces of the
+    // map ranges whose destination falls in it, plus the pieces of the range itself
+    // not covered by any source range, as those are converted into themselves
+    pub fn inverse_range(&self, range: &Range) -> Vec<Range>
+    {
+        let mut resulting_ranges: Vec<Range> = Vec::new();
+
+        for range_info in &self.ranges {
+            let inverse = range_info.inverse();
+
+            if let Some(piece) = range.intersect(&Range::new(inverse.start_source_index, inverse.end_source_index)) {
+                let offset_destination = piece.start_index - inverse.start_source_index;
+                resulting_ranges.push(Range::new(inverse.start_destination_index + offset_destination,
+                    inverse.start_destination_index + offset_destination + (piece.end_index - piece.start_index)));
+            }
+        }
+
+        let mut next_uncovered: Option<u64> = Some(range.start_index);
+        for range_info in &self.ranges {
+            let Some(current_num) = next_uncovered else {
+                break;
+            };
+
+            if range_info.start_source_index > range.end_index {
+                break;
+            }
+            if range_info.end_source_index < current_num {
+                continue;
+            }
+            if range_info.start_source_index > current_num {
+                resulting_ranges.push(Range::new(current_num, range_info.start_source_index - 1));
+            }
+            next_uncovered = range_info.end_source_index.checked_add(1);
+        }
+
+        if let Some(current_num) = next_uncovered.filter(|current_num| *current_num <= range.end_index) {
+            resulting_ranges.push(Range::new(current_num, range.end_index));
+        }
+
+        normalize_ranges(resulting_ranges)
+    }
+
+    // Map range containing the value, or the identity gap around it ending before
+    // the next map range (or at end_index after the last one). The ranges have to
+    // be sorted by source and not overlap, so a binary search finds the candidate.
+    pub fn get_range_info(&self, value: u64, end_index:u64) -> SeedRangeInfo
+    {
+        let index = self.ranges.partition_point(|range| range.start_source_index <= value);
+
+        let previous = index.checked_sub(1).map(|index| &self.ranges[index]);
+        if let Some(range) = previous.filter(|range| value <= range.end_source_index) {
+            return range.clone();
+        }
+
+        let gap_start = previous.map_or(0, |range| range.end_source_index + 1);
+        let gap_end = self.ranges.get(index).map_or(end_index, |range| range.start_source_index - 1);
+
+        SeedRangeInfo::new(gap_start, gap_end, gap_start)
+    }
+
+    // Previous lookup scanning every range, only kept to compare against in the benchmark
+    pub fn get_range_info_linear(&self, value: u64, end_index:u64) -> SeedRangeInfo
+    {
+        let list_ranges = &self.ranges;
+
+        if list_ranges.is_empty() {
+            return SeedRangeInfo::new(0, end_index, 0);
+        }
+
+        for (i,range) in list_ranges.iter().enumerate() {
+            if (range.start_source_index <= value) && (value <= range.end_source_index) {
+                return range.clone();
+            }
+            else if value < range.start_source_index {
+
+                if i == 0{
+                    return SeedRangeInfo::new(0, 
+                        range.start_source_index-1, 
+                        0);
+                }
+                else {
+                    return SeedRangeInfo::new(list_ranges[i-1].end_source_index+1, 
+                        range.start_source_index-1, 
+                        list_ranges[i-1].end_source_index+1);
+                }
+            }
+        }
+
+        SeedRangeInfo::new(list_ranges[list_ranges.len()-1].end_source_index+1, 
+            end_index, 
+            list_ranges[list_ranges.len()-1].end_source_index+1)
+    }
+}
+
+// Section of the almanac the last line read belongs to
+fn section_name(maps: &[AlmanacMap]) -> String
+{
+    match maps.last() {
+        Some(map) => format!("{}-to-{} map", map.source, map.destination),
+        None => String::from("seeds"),
+    }
+}
+
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<AlmanacMap>,
+}
+
+impl Almanac {
+
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, SeedsDBError>
+    {
+        let mut seeds: Option<Vec<u64>> = None;
+        let mut maps: Vec<AlmanacMap> = Vec::new();
+        let mut maps_lines: Vec<Vec<MapLine>> = Vec::new();
+
+        for (i, line) in reader.lines().enumerate() {
+
+            let line_str = line.map_err(|err| SeedsDBError::from(err).at_line(section_name(&maps), i + 1))?;
+
+            if line_str.trim().is_empty() {
+                continue;
+            }
+
+            if seeds.is_none() {
+                let Some(seeds_str) = line_str.trim().strip_prefix("seeds:") else {
+                    return Err(SeedsDBError::MissingSeeds.at_line(section_name(&maps), i + 1));
+                };
+
+                seeds = Some(seeds_str.split_whitespace().map(|num| 
+                    num.parse::<u64>()).collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| SeedsDBError::from(err).at_line(section_name(&maps), i + 1))?);
+                continue;
+            }
+
+            if let Some(map) = AlmanacMap::from_header(&line_str) {
+                maps.push(map);
+                maps_lines.push(Vec::new());
+                continue;
+            }
+
+            let map_line = match maps_lines.last_mut() {
+                Some(map_lines) => MapLine::parse(i + 1, &line_str).map(|map_line| map_lines.push(map_line)),
+                None => Err(SeedsDBError::DataOutsideMap),
+            };
+            map_line.map_err(|err| err.at_line(section_name(&maps), i + 1))?;
+        }
+
+        // Validation pass, every issue of every map is reported at once
+        let mut issues: Vec<RangeIssue> = Vec::new();
+        for (map, map_lines) in maps.iter().zip(&maps_lines) {
+            issues.extend(validate_map_lines(&format!("{}-to-{}", map.source, map.destination), map_lines));
+        }
+
+        if !issues.is_empty() {
+            return Err(SeedsDBError::InvalidRanges(issues));
+        }
+
+        // A map without lines is valid and converts every number into itself
+        for (map, map_lines) in maps.iter_mut().zip(&maps_lines) {
+            map.ranges = map_lines.iter().map(|map_line| map_line.to_range_info()).collect();
+            map.ranges.sort_by_key(|range| range.start_source_index);
+        }
+
+        let almanac = Almanac {
+            seeds: seeds.ok_or(SeedsDBError::MissingSeeds)?,
+            maps,
+        };
+
+        almanac.check_chain()?;
+
+        Ok(almanac)
+    }
+
+    // The maps have to form a single chain: every category is the source of at
+    // most one map, and walking from the category no map converts into, every
+    // map is reached exactly once. The sections can come in any order.
+    fn check_chain(&self) -> Result<(), SeedsDBError>
+    {
+        let maps_by_source = self.get_maps_by_source()?;
+
+        let Some(first_map) = self.maps.first() else {
+            return Ok(());
+        };
+
+        let start = self.maps.iter()
+            .find(|map| !self.maps.iter().any(|other| other.destination == map.source))
+            .ok_or_else(|| SeedsDBError::BrokenChain(first_map.source.clone()))?;
+
+        let mut category = start.source.as_str();
+        let mut maps_visited = 0;
+        while let Some(map_index) = maps_by_source.get(category) {
+            category = self.maps[*map_index].destination.as_str();
+            maps_visited += 1;
+
+            if maps_visited > self.maps.len() {
+                break;
+            }
+        }
+
+        if maps_visited != self.maps.len() {
+            return Err(SeedsDBError::BrokenChain(category.to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn get_maps_by_source(&self) -> Result<HashMap<&str, usize>, SeedsDBError>
+    {
+        let mut maps_by_source: HashMap<&str, usize> = HashMap::new();
+
+        for (i, map) in self.maps.iter().enumerate() {
+            if maps_by_source.insert(map.source.as_str(), i).is_some() {
+                return Err(SeedsDBError::DuplicateSource(map.source.clone()));
+            }
+        }
+
+        Ok(maps_by_source)
+    }
+
+    // Maps to apply, in order, to convert a number of category `from` into category `to`
+    pub fn get_route(&self, from: &str, to: &str) -> Result<Vec<&AlmanacMap>, SeedsDBError>
+    {
+        for category in [from, to] {
+            if !self.maps.iter().any(|map| map.source == category || map.destination == category) {
+                return Err(SeedsDBError::UnknownCategory(category.to_string()));
+            }
+        }
+
+        let maps_by_source = self.get_maps_by_source()?;
+
+        let mut route: Vec<&AlmanacMap> = Vec::new();
+        let mut category = from;
+
+        while category != to {
+            match maps_by_source.get(category) {
+                Some(map_index) if route.len() < self.maps.len() => {
+                    let map = &self.maps[*map_index];
+                    route.push(map);
+                    category = map.destination.as_str();
+                },
+                _ => return Err(SeedsDBError::NoRoute { from: from.to_string(), to: to.to_string() }),
+            }
+        }
+
+        Ok(route)
+    }
+
+    // Every seed of the first line on its own, as in Part One
+    pub fn get_seed_values(&self) -> Vec<Range>
+    {
+        self.seeds.iter().map(|seed| Range::single(*seed)).collect()
+    }
+
+    // The first line read as pairs of start and length, as in Part Two
+    pub fn get_seed_ranges(&self) -> Vec<Range>
+    {
+        self.seeds.chunks_exact(2)
+            .filter(|pair| pair[1] > 0)
+            .map(|pair| Range::new(pair[0], pair[0].saturating_add(pair[1] - 1)))
+            .collect()
+    }
+
+    pub fn map_value(&self, from: &str, to: &str, value: u64) -> Result<u64, SeedsDBError>
+    {
+        Ok(self.get_route(from, to)?.iter().fold(value, |value, map| map.map_value(value)))
+    }
+
+    // Every map from `from` to `to` composed into one, so each conversion is a single binary search
+    pub fn compose_route(&self, from: &str, to: &str) -> Result<AlmanacMap, SeedsDBError>
+    {
+        let mut composed_map = AlmanacMap {
+            source: from.to_string(),
+            destination: from.to_string(),
+            ranges: Vec::new(),
+        };
+
+        for map in self.get_route(from, to)? {
+            composed_map = composed_map.compose(map)?;
+        }
+
+        Ok(composed_map)
+    }
+
+    pub fn map_range(&self, from: &str, to: &str, range: &Range) -> Result<Vec<Range>, SeedsDBError>
+    {
+        let mut ranges: Vec<Range> = vec![range.clone()];
+
+        for map in self.get_route(from, to)? {
+            ranges = ranges.iter().flat_map(|range| map.map_range(range)).collect();
+        }
+
+        Ok(ranges)
+    }
+
+    // Ranges of category `from` whose numbers end up inside `range` of category `to`,
+    // e.g. get_preimage("seed", "location", &Range::single(35)) gives the seeds planted at 35
+    pub fn get_preimage(&self, from: &str, to: &str, range: &Range) -> Result<Vec<Range>, SeedsDBError>
+    {
+        let mut ranges: Vec<Range> = vec![range.clone()];
+
+        for map in self.get_route(from, to)?.iter().rev() {
+            ranges = normalize_ranges(ranges.iter().flat_map(|range| map.inverse_range(range)).collect());
+        }
+
+        Ok(ranges)
+    }
+
+    // Same answer as min_location_for but searching from the locations backwards:
+    // the locations are split in intervals at the limits of the last map and walked
+    // in ascending order, the first interval fed by any of the seeds holds the minimum
+    pub fn min_location_reverse(&self, seed_ranges: &[Range]) -> Result<Option<u64>, SeedsDBError>
+    {
+        let route = self.get_route("seed", "location")?;
+        let seed_ranges = normalize_ranges(seed_ranges.to_vec());
+
+        let mut limits: Vec<u64> = vec![0];
+        if let Some(last_map) = route.last() {
+            for range_info in &last_map.ranges {
+                let inverse = range_info.inverse();
+                limits.extend([range_info.start_source_index, inverse.start_source_index]);
+                limits.extend(range_info.end_source_index.checked_add(1));
+                limits.extend(inverse.end_source_index.checked_add(1));
+            }
+        }
+        limits.sort_unstable();
+        limits.dedup();
+
+        for (i, start_index) in limits.iter().enumerate() {
+            let end_index = limits.get(i + 1).map_or(u64::MAX, |next| next - 1);
+
+            let seeds = self.get_preimage("seed", "location", &Range::new(*start_index, end_index))?;
+            let seeds_planted = intersect_ranges(&seeds, &seed_ranges);
+
+            if !seeds_planted.is_empty() {
+                return Ok(Some(self.min_location_for(&seeds_planted)?));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn min_location_for(&self, seed_ranges: &[Range]) -> Result<u64, SeedsDBError>
+    {
+        let mut min_location = u64::MAX;
+
+        for seed_range in seed_ranges {
+            for range in self.map_range("seed", "location", seed_range)? {
+                min_location = min_location.min(range.start_index);
+            }
+        }
+
+        Ok(min_location)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use super::*;
+    use crate::algorithms::generator::Generator;
+
+    const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    const SAMPLES_PER_RANGE: usize = 1000;
+
+    fn almanacs() -> Vec<Almanac> {
+        let input = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/data/input.txt")).unwrap();
+
+        vec![Almanac::from_reader(EXAMPLE.as_bytes()).unwrap(), Almanac::from_reader(BufReader::new(input)).unwrap()]
+    }
+
+    // The seeds, both ends of every seed range and random seeds inside them
+    fn get_seeds_to_check(almanac: &Almanac) -> Vec<(u64, Option<Range>)> {
+        let mut seeds_to_check: Vec<(u64, Option<Range>)> = almanac.seeds.iter().map(|seed| (*seed, None)).collect();
+
+        let mut generator = Generator::new(0x9e37_79b9_7f4a_7c15);
+        for seed_range in almanac.get_seed_ranges() {
+            let len = seed_range.end_index - seed_range.start_index + 1;
+
+            seeds_to_check.push((seed_range.start_index, Some(seed_range.clone())));
+            seeds_to_check.push((seed_range.end_index, Some(seed_range.clone())));
+            for _ in 0 .. SAMPLES_PER_RANGE {
+                seeds_to_check.push((seed_range.start_index + generator.next_below(len), Some(seed_range.clone())));
+            }
+        }
+
+        seeds_to_check
+    }
+
+    #[test]
+    fn example_answers() {
+        let almanac = Almanac::from_reader(EXAMPLE.as_bytes()).unwrap();
+
+        assert_eq!(almanac.map_value("seed", "soil", 79).unwrap(), 81);
+        assert_eq!(almanac.map_value("seed", "location", 13).unwrap(), 35);
+        assert_eq!(almanac.min_location_for(&almanac.get_seed_values()).unwrap(), 35);
+        assert_eq!(almanac.min_location_for(&almanac.get_seed_ranges()).unwrap(), 46);
+    }
+
+    #[test]
+    fn point_and_range_paths_agree() {
+        for almanac in almanacs() {
+            for (seed, seed_range) in get_seeds_to_check(&almanac) {
+                let location = almanac.map_value("seed", "location", seed).unwrap();
+
+                assert_eq!(almanac.map_range("seed", "location", &Range::single(seed)).unwrap(),
+                    vec![Range::single(location)], "seed {seed}");
+
+                if let Some(seed_range) = seed_range {
+                    let location_ranges = almanac.map_range("seed", "location", &seed_range).unwrap();
+                    assert!(location_ranges.iter().any(|range| range.contains(location)),
+                        "seed {seed} goes to location {location}, outside of the locations of {seed_range:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn forward_and_inverse_paths_agree() {
+        for almanac in almanacs() {
+            for (seed, _) in get_seeds_to_check(&almanac) {
+                let location = almanac.map_value("seed", "location", seed).unwrap();
+                let seeds = almanac.get_preimage("seed", "location", &Range::single(location)).unwrap();
+
+                assert!(seeds.iter().any(|range| range.contains(seed)),
+                    "seed {seed} goes to location {location} but is not among its seeds {seeds:?}");
+            }
+
+            for seed_ranges in [almanac.get_seed_values(), almanac.get_seed_ranges()] {
+                let min_location = almanac.min_location_for(&seed_ranges).unwrap();
+                let seeds = almanac.get_preimage("seed", "location", &Range::single(min_location)).unwrap();
+
+                assert!(!intersect_ranges(&seeds, &normalize_ranges(seed_ranges.clone())).is_empty(),
+                    "no seed is planted at the minimum location {min_location}");
+                assert_eq!(almanac.min_location_reverse(&seed_ranges).unwrap(), Some(min_location));
+            }
+        }
+    }
+
+    #[test]
+    fn chained_and_composed_maps_agree() {
+        for almanac in almanacs() {
+            let composed_map = almanac.compose_route("seed", "location").unwrap();
+
+            for (seed, _) in get_seeds_to_check(&almanac) {
+                assert_eq!(composed_map.map_value(seed), almanac.map_value("seed", "location", seed).unwrap(),
+                    "seed {seed}");
+            }
+
+            for seed_ranges in [almanac.get_seed_values(), almanac.get_seed_ranges()] {
+                let min_location_composed = seed_ranges.iter()
+                    .flat_map(|seed_range| composed_map.map_range(seed_range))
+                    .map(|range| range.start_index)
+                    .min();
+
+                assert_eq!(min_location_composed, Some(almanac.min_location_for(&seed_ranges).unwrap()));
+            }
+        }
+    }
+
+    fn range_info_fields(range_info: &SeedRangeInfo) -> (u64, u64, u64) {
+        (range_info.start_source_index, range_info.end_source_index, range_info.start_destination_index)
+    }
+
+    // Up to 7 sorted ranges of up to 50 numbers (no ranges at all in some maps), with
+    // gaps of up to 20 numbers between them or none so that neighbour ranges touch
+    fn random_map(generator: &mut Generator) -> AlmanacMap {
+        let mut ranges: Vec<SeedRangeInfo> = Vec::new();
+        let mut start = generator.next_below(20);
+
+        for _ in 0 .. generator.next_below(8) {
+            let end = start + generator.next_below(50);
+            ranges.push(SeedRangeInfo::new(start, end, generator.next_below(10_000)));
+            start = end + 1 + generator.next_below(20) * generator.next_below(2);
+        }
+
+        AlmanacMap { source: String::from("a"), destination: String::from("b"), ranges }
+    }
+
+    #[test]
+    fn binary_and_linear_range_lookups_agree() {
+        const END_INDEX: u64 = 1_000_000;
+        let mut generator = Generator::new(0x2545_f491_4f6c_dd1d);
+
+        for map_i in 0 .. 2000 {
+            let map = random_map(&mut generator);
+
+            // Edges of every range and of the gaps, the numbers next to them and some random ones
+            let mut values: Vec<u64> = vec![0, END_INDEX];
+            for range in &map.ranges {
+                values.extend([range.start_source_index.saturating_sub(1), range.start_source_index,
+                    range.end_source_index, range.end_source_index + 1]);
+            }
+            let last = map.ranges.last().map_or(0, |range| range.end_source_index);
+            values.extend((0 .. 20).map(|_| generator.next_below(last + 30)));
+
+            for value in values {
+                assert_eq!(range_info_fields(&map.get_range_info(value, END_INDEX)),
+                    range_info_fields(&map.get_range_info_linear(value, END_INDEX)),
+                    "value {value} of map {map_i} {:?}", map.ranges);
+            }
+        }
+    }
+}
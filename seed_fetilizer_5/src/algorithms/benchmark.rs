@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant};
+
+use crate::algorithms::{
+    almanac::AlmanacMap,
+    common::SeedRangeInfo,
+    generator::Generator};
+
+const STAGES: usize = 7;
+
+pub struct BenchmarkResult {
+    pub stages: usize,
+    pub ranges_per_stage: usize,
+    pub lookups: usize,
+    pub linear_time: Duration,
+    pub binary_search_time: Duration,
+}
+
+// Sorted, non overlapping source ranges with gaps between some of them
+fn generate_map(generator: &mut Generator, ranges_per_stage: usize) -> AlmanacMap
+{
+    let mut ranges: Vec<SeedRangeInfo> = Vec::with_capacity(ranges_per_stage);
+    let mut start_source_index: u64 = generator.next_below(1_000);
+
+    for _ in 0 .. ranges_per_stage {
+        let length = 1 + generator.next_below(100_000);
+        ranges.push(SeedRangeInfo::new(start_source_index, start_source_index + length - 1,
+            generator.next_below(1 << 40)));
+
+        start_source_index += length + if generator.next_below(2) == 0 {0} else {generator.next_below(10_000)};
+    }
+
+    AlmanacMap { source: String::new(), destination: String::new(), ranges }
+}
+
+// Looks up the same random values in every stage with both strategies
+pub fn run_lookup_benchmark(ranges_per_stage: usize, lookups: usize) -> Result<BenchmarkResult, String>
+{
+    let mut generator = Generator::new(0x2545_f491_4f6c_dd1d);
+
+    let maps: Vec<AlmanacMap> = (0 .. STAGES).map(|_| generate_map(&mut generator, ranges_per_stage)).collect();
+    let values: Vec<u64> = maps.iter()
+        .map(|map| map.ranges.last().map_or(0, |range| range.end_source_index) + 1_000)
+        .flat_map(|limit| (0 .. lookups / STAGES).map(|_| generator.next_below(limit)).collect::<Vec<_>>())
+        .collect();
+
+    let start = Instant::now();
+    let mut linear_checksum: u64 = 0;
+    for map in &maps {
+        for value in &values {
+            linear_checksum = linear_checksum.wrapping_add(map.get_range_info_linear(*value, u64::MAX).start_destination_index);
+        }
+    }
+    let linear_time = start.elapsed();
+
+    let start = Instant::now();
+    let mut binary_search_checksum: u64 = 0;
+    for map in &maps {
+        for value in &values {
+            binary_search_checksum = binary_search_checksum.wrapping_add(map.get_range_info(*value, u64::MAX).start_destination_index);
+        }
+    }
+    let binary_search_time = start.elapsed();
+
+    if linear_checksum != binary_search_checksum {
+        return Err("Linear scan and binary search found different ranges".to_string());
+    }
+
+    Ok(BenchmarkResult {
+        stages: STAGES,
+        ranges_per_stage,
+        lookups: values.len() * STAGES,
+        linear_time,
+        binary_search_time,
+    })
+}
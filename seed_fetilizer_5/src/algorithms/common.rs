@@ -1,6 +1,4 @@
-use super::error::SeedsDBError;
-
- #[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct SeedRangeInfo {
     pub start_source_index: u64,
     pub end_source_index: u64,
@@ -17,51 +15,86 @@ impl SeedRangeInfo {
             start_destination_index: start_dest,
         }
     } 
+
+    // Same range read backwards, from destination to source
+    pub fn inverse(&self) -> Self
+    {
+        SeedRangeInfo::new(self.start_destination_index,
+            self.start_destination_index + (self.end_source_index - self.start_source_index),
+            self.start_source_index)
+    }
 }
 
-#[derive(Debug)]
-pub enum SeedsListType{
-    SeedToSoil,
-    SoilToFertilizer,
-    FertilizerToWater,
-    WaterToLight,
-    LightToTemperature,
-    TemperatureToHumidity,
-    HumidityToLocation,
+// Inclusive range of numbers of one category, a single number is a range of length 1
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Range {
+    pub start_index: u64,
+    pub end_index: u64,
 }
 
-impl SeedsListType {
-    
-    pub fn get_all_variants() -> Vec<SeedsListType> {
+impl Range {
+
+    pub fn new(start_index: u64, end_index: u64) -> Self
+    {
+        Range { start_index, end_index }
+    }
+
+    pub fn single(value: u64) -> Self
+    {
+        Range { start_index: value, end_index: value }
+    }
+
+    pub fn contains(&self, value: u64) -> bool
+    {
+        self.start_index <= value && value <= self.end_index
+    }
 
-        vec![SeedsListType:: SeedToSoil, SeedsListType::SoilToFertilizer, 
-             SeedsListType::FertilizerToWater, SeedsListType::WaterToLight, 
-             SeedsListType::LightToTemperature, SeedsListType::TemperatureToHumidity, 
-             SeedsListType::HumidityToLocation]
+    pub fn intersect(&self, other: &Range) -> Option<Range>
+    {
+        let start_index = self.start_index.max(other.start_index);
+        let end_index = self.end_index.min(other.end_index);
+
+        if start_index <= end_index {Some(Range::new(start_index, end_index))} else {None}
+    }
+}
+
+// Sorts the ranges and joins the ones overlapping or touching each other
+pub fn normalize_ranges(mut ranges: Vec<Range>) -> Vec<Range>
+{
+    ranges.sort_by_key(|range| range.start_index);
+
+    let mut result: Vec<Range> = Vec::new();
+    for range in ranges {
+        match result.last_mut() {
+            Some(last) if last.end_index.checked_add(1).is_none_or(|next| range.start_index <= next) => {
+                last.end_index = last.end_index.max(range.end_index);
+            },
+            _ => result.push(range),
+        }
     }
+
+    result
 }
 
-pub trait SeedsAlgorithm {
-
-    fn populate_map_based(map_db: &mut Vec<SeedRangeInfo>, data: &str) -> Result<(), SeedsDBError>{
-        
-        // Get the position ranges to populate the seeds
-        let range: Vec<u64> = data.split_whitespace().map(|num| 
-            num.parse::<u64>()).collect::<Result<Vec<_>, _>>()?;
-        
-        // Populate the map
-        if range.len() == 3 {
-            let temp_range_info :SeedRangeInfo = 
-                SeedRangeInfo::new(range[1], 
-                        range[1] + range[2] -1, 
-                                   range[0]);
-
-            map_db.push(temp_range_info);
+// Both lists have to be normalized
+pub fn intersect_ranges(ranges_a: &[Range], ranges_b: &[Range]) -> Vec<Range>
+{
+    let mut result: Vec<Range> = Vec::new();
+    let (mut index_a, mut index_b) = (0, 0);
+
+    while index_a < ranges_a.len() && index_b < ranges_b.len() {
+
+        if let Some(range) = ranges_a[index_a].intersect(&ranges_b[index_b]) {
+            result.push(range);
+        }
+
+        if ranges_a[index_a].end_index < ranges_b[index_b].end_index {
+            index_a += 1;
         }
         else {
-            return Err(SeedsDBError::ListDataRangeBadSize)
+            index_b += 1;
         }
-        
-        Ok(())
     }
-}
\ No newline at end of file
+
+    result
+}
@@ -1,9 +1,69 @@
-use std::num::{ParseIntError, TryFromIntError};
+use std::fmt;
+use std::num::ParseIntError;
 
+use crate::algorithms::validation::RangeIssue;
+
+#[derive(Debug)]
 pub enum SeedsDBError {
-    ListDataRangeBadSize,
+    ListDataRangeBadSize(usize),
     Parse(ParseIntError),
-    TryInt(TryFromIntError),
+    Io(std::io::Error),
+    MissingSeeds,
+    DataOutsideMap,
+    DuplicateSource(String),
+    BrokenChain(String),
+    UnknownCategory(String),
+    NoRoute { from: String, to: String },
+    InvalidRanges(Vec<RangeIssue>),
+    // Any of the others found while reading a line of the almanac,
+    // section is "seeds" or the header of the map, like "seed-to-soil map"
+    AtLine { section: String, line: usize, error: Box<SeedsDBError> },
+}
+
+impl SeedsDBError {
+
+    pub fn at_line(self, section: String, line: usize) -> SeedsDBError
+    {
+        SeedsDBError::AtLine { section, line, error: Box::new(self) }
+    }
+}
+
+impl fmt::Display for SeedsDBError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeedsDBError::ListDataRangeBadSize(found) =>
+                write!(f, "expected '<destination> <source> <length>' but found {found} numbers"),
+            SeedsDBError::Parse(err) => write!(f, "not a valid number: {err}"),
+            SeedsDBError::Io(err) => write!(f, "could not read the almanac: {err}"),
+            SeedsDBError::MissingSeeds => write!(f, "the almanac does not start with 'seeds:'"),
+            SeedsDBError::DataOutsideMap => write!(f, "numbers outside of any map, a 'X-to-Y map:' header is missing"),
+            SeedsDBError::DuplicateSource(category) => write!(f, "more than one map converts from {category}"),
+            SeedsDBError::BrokenChain(category) => write!(f, "the maps do not form a single chain, it breaks at {category}"),
+            SeedsDBError::UnknownCategory(category) => write!(f, "no map converts from or into {category}"),
+            SeedsDBError::NoRoute { from, to } => write!(f, "there is no way from {from} to {to}"),
+            SeedsDBError::InvalidRanges(issues) => {
+                write!(f, "the almanac has {} invalid range(s)", issues.len())?;
+                for issue in issues {
+                    write!(f, "\n  {issue}")?;
+                }
+                Ok(())
+            },
+            SeedsDBError::AtLine { section, line, error } => write!(f, "line {line} ({section}): {error}"),
+        }
+    }
+}
+
+impl std::error::Error for SeedsDBError {
+
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SeedsDBError::Parse(err) => Some(err),
+            SeedsDBError::Io(err) => Some(err),
+            SeedsDBError::AtLine { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
 }
 
 impl From<ParseIntError> for SeedsDBError {
@@ -13,9 +73,70 @@ impl From<ParseIntError> for SeedsDBError {
     }
 }
 
-impl From<TryFromIntError> for SeedsDBError {
+impl From<std::io::Error> for SeedsDBError {
+
+    fn from(err: std::io::Error) -> SeedsDBError {
+        SeedsDBError::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::almanac::Almanac;
+
+    const VALID: &str = "seeds: 1 2\n\na-to-b map:\n5 0 10\n\nb-to-c map:\n";
+
+    fn read_error(data: &[u8]) -> String {
+        match Almanac::from_reader(data) {
+            Ok(_) => panic!("{:?} was read without errors", String::from_utf8_lossy(data)),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    fn route_error(from: &str, to: &str) -> String {
+        let almanac = Almanac::from_reader(VALID.as_bytes()).unwrap();
+
+        match almanac.map_value(from, to, 1) {
+            Ok(value) => panic!("{from} 1 was mapped to {to} {value}"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn seeds_errors() {
+        assert_eq!(read_error(b""), "the almanac does not start with 'seeds:'");
+        assert_eq!(read_error(b"a-to-b map:\n1 2 3\n"), "line 1 (seeds): the almanac does not start with 'seeds:'");
+        assert_eq!(read_error(b"seeds: 1 x\n"), "line 1 (seeds): not a valid number: invalid digit found in string");
+        assert_eq!(read_error(b"seeds: 1\n1 2 3\n"),
+            "line 2 (seeds): numbers outside of any map, a 'X-to-Y map:' header is missing");
+    }
+
+    #[test]
+    fn map_line_errors() {
+        assert_eq!(read_error(b"seeds: 1\n\na-to-b map:\n1 2 x\n"),
+            "line 4 (a-to-b map): not a valid number: invalid digit found in string");
+        assert_eq!(read_error(b"seeds: 1\n\na-to-b map:\n1 2 3\n4 5\n"),
+            "line 5 (a-to-b map): expected '<destination> <source> <length>' but found 2 numbers");
+        assert_eq!(read_error(b"seeds: 1\n\na-to-b map:\n\xff\n"),
+            "line 4 (a-to-b map): could not read the almanac: stream did not contain valid UTF-8");
+    }
+
+    #[test]
+    fn chain_errors() {
+        assert_eq!(read_error(b"seeds: 1\na-to-b map:\na-to-c map:\n"), "more than one map converts from a");
+        assert_eq!(read_error(b"seeds: 1\na-to-b map:\nc-to-d map:\n"),
+            "the maps do not form a single chain, it breaks at b");
+    }
+
+    #[test]
+    fn range_errors() {
+        assert_eq!(read_error(b"seeds: 1\na-to-b map:\n0 10 5\n20 12 0\n"),
+            "the almanac has 1 invalid range(s)\n  line 4 (a-to-b map): range has length 0");
+    }
 
-    fn from(err: TryFromIntError) -> SeedsDBError {
-        SeedsDBError::TryInt(err)
+    #[test]
+    fn route_errors() {
+        assert_eq!(route_error("c", "a"), "there is no way from c to a");
+        assert_eq!(route_error("a", "z"), "no map converts from or into z");
     }
 }
@@ -0,0 +1,283 @@
+use crate::algorithms::{
+    almanac::Almanac,
+    common::{Range, SeedRangeInfo},
+    error::SeedsDBError};
+
+// What converted a piece: one of the map ranges or the identity gap around the piece
+#[derive(Clone, Debug)]
+pub enum Applied {
+    MapRange(SeedRangeInfo),
+    IdentityGap(Range),
+}
+
+#[derive(Clone, Debug)]
+pub struct TracePiece {
+    // Numbers of the first category the piece comes from
+    pub origin: Range,
+    pub source: Range,
+    pub destination: Range,
+    pub applied: Applied,
+}
+
+pub struct TraceStep {
+    pub source: String,
+    pub destination: String,
+    pub pieces: Vec<TracePiece>,
+}
+
+pub struct Trace {
+    pub from: String,
+    pub to: String,
+    pub range: Range,
+    pub steps: Vec<TraceStep>,
+}
+
+// Follows the range through every map from `from` to `to`, splitting it where the
+// map ranges split it and keeping for each piece the numbers it started as
+pub fn get_trace(almanac: &Almanac, from: &str, to: &str, range: &Range) -> Result<Trace, SeedsDBError>
+{
+    let mut current: Vec<(Range, Range)> = vec![(range.clone(), range.clone())];
+    let mut steps: Vec<TraceStep> = Vec::new();
+
+    for map in almanac.get_route(from, to)? {
+        let mut pieces: Vec<TracePiece> = Vec::new();
+
+        for (origin, value) in &current {
+            for piece in map.split_range(value) {
+                let length = piece.end_source_index - piece.start_source_index;
+                let origin_start = origin.start_index + (piece.start_source_index - value.start_index);
+
+                let applied = match map.find_range(piece.start_source_index) {
+                    Some(range_info) => Applied::MapRange(range_info.clone()),
+                    None => {
+                        let gap = map.get_range_info(piece.start_source_index, u64::MAX);
+                        Applied::IdentityGap(Range::new(gap.start_source_index, gap.end_source_index))
+                    },
+                };
+
+                pieces.push(TracePiece {
+                    origin: Range::new(origin_start, origin_start + length),
+                    source: Range::new(piece.start_source_index, piece.end_source_index),
+                    destination: Range::new(piece.start_destination_index, piece.start_destination_index + length),
+                    applied,
+                });
+            }
+        }
+
+        current = pieces.iter().map(|piece| (piece.origin.clone(), piece.destination.clone())).collect();
+        steps.push(TraceStep { source: map.source.clone(), destination: map.destination.clone(), pieces });
+    }
+
+    Ok(Trace { from: from.to_string(), to: to.to_string(), range: range.clone(), steps })
+}
+
+fn format_range(range: &Range) -> String
+{
+    if range.start_index == range.end_index {
+        range.start_index.to_string()
+    }
+    else {
+        format!("{}-{}", range.start_index, range.end_index)
+    }
+}
+
+// Map ranges are written as in the almanac, "<destination> <source> <length>"
+fn format_applied(applied: &Applied) -> String
+{
+    match applied {
+        Applied::MapRange(range_info) => format!("range {} {} {}", range_info.start_destination_index,
+            range_info.start_source_index, range_info.end_source_index - range_info.start_source_index + 1),
+        Applied::IdentityGap(gap) => format!("identity gap {}", format_range(gap)),
+    }
+}
+
+pub fn trace_to_table(trace: &Trace) -> String
+{
+    let mut rows: Vec<[String; 4]> = vec![[trace.from.clone(), String::from("source"),
+        String::from("destination"), String::from("applied")]];
+
+    for step in &trace.steps {
+        for piece in &step.pieces {
+            rows.push([
+                format!("{} {}", trace.from, format_range(&piece.origin)),
+                format!("{} {}", step.source, format_range(&piece.source)),
+                format!("{} {}", step.destination, format_range(&piece.destination)),
+                format_applied(&piece.applied),
+            ]);
+        }
+    }
+
+    let mut widths = [0; 4];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut result = String::new();
+    for row in &rows {
+        let line = format!("{:<w0$} | {:<w1$} | {:<w2$} | {}", row[0], row[1], row[2], row[3],
+            w0 = widths[0], w1 = widths[1], w2 = widths[2]);
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+
+    result
+}
+
+fn json_string(value: &str) -> String
+{
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn json_range(range: &Range) -> String
+{
+    format!("{{\"start\": {}, \"end\": {}}}", range.start_index, range.end_index)
+}
+
+pub fn trace_to_json(trace: &Trace) -> String
+{
+    let mut steps: Vec<String> = Vec::new();
+
+    for step in &trace.steps {
+        let pieces: Vec<String> = step.pieces.iter().map(|piece| {
+            let applied = match &piece.applied {
+                Applied::MapRange(range_info) => format!("{{\"type\": \"range\", \"destination\": {}, \"source\": {}, \"length\": {}}}",
+                    range_info.start_destination_index, range_info.start_source_index,
+                    range_info.end_source_index - range_info.start_source_index + 1),
+                Applied::IdentityGap(gap) => format!("{{\"type\": \"identity\", \"start\": {}, \"end\": {}}}",
+                    gap.start_index, gap.end_index),
+            };
+
+            format!("        {{\"origin\": {}, \"source\": {}, \"destination\": {}, \"applied\": {applied}}}",
+                json_range(&piece.origin), json_range(&piece.source), json_range(&piece.destination))
+        }).collect();
+
+        steps.push(format!("    {{\"source\": {}, \"destination\": {}, \"pieces\": [\n{}\n    ]}}",
+            json_string(&step.source), json_string(&step.destination), pieces.join(",\n")));
+    }
+
+    format!("{{\"from\": {}, \"to\": {}, \"range\": {}, \"steps\": [\n{}\n]}}\n",
+        json_string(&trace.from), json_string(&trace.to), json_range(&trace.range), steps.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    fn example_trace(from: &str, to: &str, range: Range) -> Trace {
+        let almanac = Almanac::from_reader(EXAMPLE.as_bytes()).unwrap();
+
+        get_trace(&almanac, from, to, &range).unwrap()
+    }
+
+    // (origin, destination) of every piece of the step
+    fn get_pieces(step: &TraceStep) -> Vec<(Range, Range)> {
+        step.pieces.iter().map(|piece| (piece.origin.clone(), piece.destination.clone())).collect()
+    }
+
+    #[test]
+    fn seed_trace() {
+        let trace = example_trace("seed", "location", Range::single(79));
+
+        let stages: Vec<(&str, u64)> = trace.steps.iter()
+            .map(|step| (step.destination.as_str(), step.pieces[0].destination.start_index))
+            .collect();
+        assert_eq!(stages, vec![("soil", 81), ("fertilizer", 81), ("water", 81), ("light", 74),
+            ("temperature", 78), ("humidity", 78), ("location", 82)]);
+
+        assert!(trace.steps.iter().all(|step| step.pieces.len() == 1 && step.pieces[0].origin == Range::single(79)));
+        assert!(matches!(&trace.steps[1].pieces[0].applied,
+            Applied::IdentityGap(gap) if *gap == Range::new(54, u64::MAX)));
+        assert!(matches!(&trace.steps[3].pieces[0].applied,
+            Applied::MapRange(range_info) if range_info.start_source_index == 25 && range_info.start_destination_index == 18));
+    }
+
+    #[test]
+    fn range_trace() {
+        let trace = example_trace("seed", "location", Range::new(79, 92));
+
+        assert_eq!(get_pieces(&trace.steps[3]), vec![(Range::new(79, 92), Range::new(74, 87))]);
+
+        // Light 74-76 and 77-87 fall in different light-to-temperature ranges
+        assert_eq!(get_pieces(&trace.steps[4]), vec![
+            (Range::new(79, 81), Range::new(78, 80)),
+            (Range::new(82, 92), Range::new(45, 55)),
+        ]);
+
+        // Humidity 46-56 splits again between the identity gap and the first range
+        assert_eq!(get_pieces(&trace.steps[6]), vec![
+            (Range::new(79, 81), Range::new(82, 84)),
+            (Range::new(82, 91), Range::new(46, 55)),
+            (Range::new(92, 92), Range::new(60, 60)),
+        ]);
+    }
+
+    #[test]
+    fn unknown_route() {
+        let almanac = Almanac::from_reader(EXAMPLE.as_bytes()).unwrap();
+
+        assert!(get_trace(&almanac, "seed", "sunlight", &Range::single(79)).is_err());
+    }
+
+    #[test]
+    fn table_output() {
+        let trace = example_trace("seed", "soil", Range::new(48, 51));
+
+        assert_eq!(trace_to_table(&trace), "\
+seed       | source     | destination | applied
+seed 48-49 | seed 48-49 | soil 48-49  | identity gap 0-49
+seed 50-51 | seed 50-51 | soil 52-53  | range 52 50 48
+");
+    }
+
+    #[test]
+    fn json_output() {
+        let trace = example_trace("seed", "soil", Range::single(79));
+
+        assert_eq!(trace_to_json(&trace), r#"{"from": "seed", "to": "soil", "range": {"start": 79, "end": 79}, "steps": [
+    {"source": "seed", "destination": "soil", "pieces": [
+        {"origin": {"start": 79, "end": 79}, "source": {"start": 79, "end": 79}, "destination": {"start": 81, "end": 81}, "applied": {"type": "range", "destination": 52, "source": 50, "length": 48}}
+    ]}
+]}
+"#);
+
+        let trace = example_trace("seed", "soil", Range::new(48, 49));
+        assert!(trace_to_json(&trace).contains(r#""applied": {"type": "identity", "start": 0, "end": 49}"#));
+    }
+}
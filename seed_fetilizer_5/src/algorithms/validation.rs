@@ -0,0 +1,204 @@
+use std::fmt;
+
+use crate::algorithms::{
+    common::SeedRangeInfo,
+    error::SeedsDBError};
+
+// A "<destination> <source> <length>" line of a map as written in the almanac
+#[derive(Clone, Debug)]
+pub struct MapLine {
+    pub line: usize,
+    pub destination: u64,
+    pub source: u64,
+    pub length: u64,
+}
+
+impl MapLine {
+
+    pub fn parse(line: usize, data: &str) -> Result<Self, SeedsDBError>
+    {
+        let range: Vec<u64> = data.split_whitespace().map(|num| 
+            num.parse::<u64>()).collect::<Result<Vec<_>, _>>()?;
+
+        if range.len() != 3 {
+            return Err(SeedsDBError::ListDataRangeBadSize(range.len()))
+        }
+
+        Ok(MapLine { line, destination: range[0], source: range[1], length: range[2] })
+    }
+
+    // Only valid once the line went through validate_map_lines
+    pub fn to_range_info(&self) -> SeedRangeInfo
+    {
+        SeedRangeInfo::new(self.source, self.source + (self.length - 1), self.destination)
+    }
+}
+
+#[derive(Debug)]
+pub enum RangeIssue {
+    ZeroLength { map: String, line: usize },
+    SourceOverflow { map: String, line: usize },
+    DestinationOverflow { map: String, line: usize },
+    Overlap { map: String, line: usize, other_line: usize },
+}
+
+impl fmt::Display for RangeIssue {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeIssue::ZeroLength { map, line } =>
+                write!(f, "line {line} ({map} map): range has length 0"),
+            RangeIssue::SourceOverflow { map, line } =>
+                write!(f, "line {line} ({map} map): source range goes past {}", u64::MAX),
+            RangeIssue::DestinationOverflow { map, line } =>
+                write!(f, "line {line} ({map} map): destination range goes past {}", u64::MAX),
+            RangeIssue::Overlap { map, line, other_line } =>
+                write!(f, "line {line} ({map} map): source range overlaps the one in line {other_line}"),
+        }
+    }
+}
+
+// Every problem found in the lines of one map. Lines with a length of 0 or
+// going past u64::MAX are reported and left out of the overlap check.
+pub fn validate_map_lines(map: &str, map_lines: &[MapLine]) -> Vec<RangeIssue>
+{
+    let mut issues: Vec<RangeIssue> = Vec::new();
+    let mut valid_lines: Vec<&MapLine> = Vec::new();
+
+    for map_line in map_lines {
+        let line = map_line.line;
+
+        if map_line.length == 0 {
+            issues.push(RangeIssue::ZeroLength { map: map.to_string(), line });
+            continue;
+        }
+
+        let mut valid = true;
+        if map_line.source.checked_add(map_line.length - 1).is_none() {
+            issues.push(RangeIssue::SourceOverflow { map: map.to_string(), line });
+            valid = false;
+        }
+        if map_line.destination.checked_add(map_line.length - 1).is_none() {
+            issues.push(RangeIssue::DestinationOverflow { map: map.to_string(), line });
+            valid = false;
+        }
+
+        if valid {
+            valid_lines.push(map_line);
+        }
+    }
+
+    valid_lines.sort_by_key(|map_line| map_line.source);
+
+    // Sorted by source, a range overlaps the one before it if it starts before that one ends
+    let mut furthest: Option<&MapLine> = None;
+    for map_line in valid_lines {
+        if let Some(previous) = furthest {
+            if map_line.source <= previous.source + (previous.length - 1) {
+                issues.push(RangeIssue::Overlap { map: map.to_string(), line: map_line.line, other_line: previous.line });
+            }
+        }
+
+        let end = map_line.source + (map_line.length - 1);
+        if furthest.is_none_or(|previous| end > previous.source + (previous.length - 1)) {
+            furthest = Some(map_line);
+        }
+    }
+
+    issues.sort_by_key(|issue| match issue {
+        RangeIssue::ZeroLength { line, .. } | RangeIssue::SourceOverflow { line, .. } |
+        RangeIssue::DestinationOverflow { line, .. } | RangeIssue::Overlap { line, .. } => *line,
+    });
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::almanac::Almanac;
+
+    fn map_line(line: usize, destination: u64, source: u64, length: u64) -> MapLine {
+        MapLine { line, destination, source, length }
+    }
+
+    // (kind, line, other line) of every issue, in the order they are reported
+    fn summary(issues: &[RangeIssue]) -> Vec<(&'static str, usize, Option<usize>)> {
+        issues.iter().map(|issue| match issue {
+            RangeIssue::ZeroLength { line, .. } => ("zero", *line, None),
+            RangeIssue::SourceOverflow { line, .. } => ("source", *line, None),
+            RangeIssue::DestinationOverflow { line, .. } => ("destination", *line, None),
+            RangeIssue::Overlap { line, other_line, .. } => ("overlap", *line, Some(*other_line)),
+        }).collect()
+    }
+
+    #[test]
+    fn valid_ranges() {
+        let map_lines = [map_line(3, 50, 98, 2), map_line(4, 52, 50, 48), map_line(5, 0, 0, 50)];
+        assert!(validate_map_lines("seed-to-soil", &map_lines).is_empty());
+
+        // Ranges ending right at u64::MAX still fit
+        let map_lines = [map_line(3, u64::MAX, u64::MAX - 9, 1), map_line(4, 0, u64::MAX - 9, 10)];
+        assert_eq!(summary(&validate_map_lines("a-to-b", &map_lines)), vec![("overlap", 4, Some(3))]);
+        assert!(validate_map_lines("a-to-b", &map_lines[1 ..]).is_empty());
+    }
+
+    #[test]
+    fn overlapping_ranges() {
+        // The second line overlaps the first one, even though it comes first in the file
+        let map_lines = [map_line(8, 0, 12, 5), map_line(7, 100, 10, 5)];
+        let issues = validate_map_lines("a-to-b", &map_lines);
+        assert_eq!(summary(&issues), vec![("overlap", 8, Some(7))]);
+        assert_eq!(issues[0].to_string(), "line 8 (a-to-b map): source range overlaps the one in line 7");
+
+        // Every range inside a longer one overlaps it, not only the one next to it
+        let map_lines = [map_line(3, 0, 0, 100), map_line(4, 0, 10, 5), map_line(5, 0, 20, 5), map_line(6, 0, 100, 5)];
+        assert_eq!(summary(&validate_map_lines("a-to-b", &map_lines)),
+            vec![("overlap", 4, Some(3)), ("overlap", 5, Some(3))]);
+    }
+
+    #[test]
+    fn overflowing_ranges() {
+        let map_lines = [map_line(3, 0, u64::MAX, 2), map_line(4, u64::MAX - 1, 0, 3), map_line(5, u64::MAX, u64::MAX, 2)];
+        let issues = validate_map_lines("a-to-b", &map_lines);
+
+        assert_eq!(summary(&issues),
+            vec![("source", 3, None), ("destination", 4, None), ("source", 5, None), ("destination", 5, None)]);
+        assert_eq!(issues[0].to_string(), format!("line 3 (a-to-b map): source range goes past {}", u64::MAX));
+        assert_eq!(issues[1].to_string(), format!("line 4 (a-to-b map): destination range goes past {}", u64::MAX));
+    }
+
+    #[test]
+    fn several_issues_are_sorted_by_line() {
+        let map_lines = [
+            map_line(2, 0, 100, 10),
+            map_line(3, 0, 40, 0),
+            map_line(4, 0, 105, 10),
+            map_line(5, 0, u64::MAX, 5),
+            map_line(6, u64::MAX, 500, 2),
+            map_line(7, 0, 0, 200),
+        ];
+
+        // Overflowing and empty ranges are left out of the overlap check
+        assert_eq!(summary(&validate_map_lines("a-to-b", &map_lines)), vec![
+            ("overlap", 2, Some(7)),
+            ("zero", 3, None),
+            ("overlap", 4, Some(7)),
+            ("source", 5, None),
+            ("destination", 6, None),
+        ]);
+    }
+
+    #[test]
+    fn issues_of_every_map_are_reported_at_once() {
+        let almanac = "seeds: 1\n\na-to-b map:\n0 10 5\n0 12 5\n\nb-to-c map:\n0 0 0\n18446744073709551615 0 2\n";
+
+        match Almanac::from_reader(almanac.as_bytes()) {
+            Ok(_) => panic!("the almanac has invalid ranges"),
+            Err(err) => assert_eq!(err.to_string(), format!("the almanac has 3 invalid range(s)\n  \
+                line 5 (a-to-b map): source range overlaps the one in line 4\n  \
+                line 8 (b-to-c map): range has length 0\n  \
+                line 9 (b-to-c map): destination range goes past {}", u64::MAX)),
+        }
+    }
+}
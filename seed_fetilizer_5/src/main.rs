@@ -107,13 +107,13 @@
 
 
 use std::fs::File;
-use std::io::{BufReader, BufRead};
-use std::collections::HashMap;
-use std::u64::MAX;
+use std::io::BufReader;
 
-use crate::algorithms::{first_part::SeedsDB,
-                        second_part::SeedsDBRanges, 
-                        common::SeedsListType};
+use crate::algorithms::{almanac::Almanac,
+                        benchmark::run_lookup_benchmark,
+                        common::Range,
+                        error::SeedsDBError,
+                        trace::{get_trace, trace_to_json, trace_to_table}};
 
 pub mod algorithms;
 
@@ -124,82 +124,123 @@ enum TypeRun {
 }
 
 fn main() -> std::io::Result<()>{
-    algorithm(TypeRun::FirstPart)?;
-    algorithm(TypeRun::SecondPart)?;
+    let args: Vec<String> = std::env::args().collect();
 
-    Ok(())
-}
-
-fn algorithm(type_run: TypeRun) -> std::io::Result<()>
-{
     // Open the file for reading
     let file: File = File::open("data/input.txt")?;
 
-    // Create a buffered reader to read the file
-    let reader: BufReader<File> = BufReader::new(file);
-
-    let mut seed_data_part_1: SeedsDB = SeedsDB::new();
-    let mut seed_data_part_2: SeedsDBRanges = SeedsDBRanges::new();
-
-    let mut map_text_to_map_type: HashMap<String, SeedsListType> = HashMap::new();
-    map_text_to_map_type.insert("seed-to-soil".to_string(), SeedsListType::SeedToSoil);
-    map_text_to_map_type.insert("soil-to-fertilizer".to_string(), SeedsListType::SoilToFertilizer);
-    map_text_to_map_type.insert("fertilizer-to-water".to_string(), SeedsListType::FertilizerToWater);
-    map_text_to_map_type.insert("water-to-light".to_string(), SeedsListType::WaterToLight);
-    map_text_to_map_type.insert("light-to-temperature".to_string(), SeedsListType::LightToTemperature);
-    map_text_to_map_type.insert("temperature-to-humidity".to_string(), SeedsListType::TemperatureToHumidity);
-    map_text_to_map_type.insert("humidity-to-location".to_string(), SeedsListType::HumidityToLocation);
+    let almanac = Almanac::from_reader(BufReader::new(file))
+        .map_err(almanac_error)?;
 
-    let mut in_data_state_flag: bool = false;
-    let mut current_map_to_fill: &SeedsListType = &SeedsListType::SeedToSoil;
-    for (i, line) in reader.lines().enumerate() {
-
-        let line_str = line?;
+    match args.get(1).map(|arg| arg.as_str()) {
+        // Convert a number between any two categories, e.g. map soil humidity 81
+        Some("map") => {
+            let (Some(from), Some(to), Some(value)) = (args.get(2), args.get(3), args.get(4)) else {
+                return Err(std::io::Error::other("Expected map <from> <to> <value> :("));
+            };
+            let value = value.parse::<u64>().map_err(std::io::Error::other)?;
+
+            let mapped_value = almanac.map_value(from, to, value)
+                .map_err(almanac_error)?;
+            println!("{from} {value} corresponds to {to} {mapped_value}");
+        },
+        // All the maps between two categories as a single one, seed to location by default
+        Some("compose") => {
+            let from = args.get(2).map(|arg| arg.as_str()).unwrap_or("seed");
+            let to = args.get(3).map(|arg| arg.as_str()).unwrap_or("location");
+
+            let composed_map = almanac.compose_route(from, to)
+                .map_err(almanac_error)?;
+            print!("{}", composed_map.to_csv());
+        },
+        // Numbers of one category converted into a number or range of a later one,
+        // e.g. preimage seed location 35 gives the seeds planted at location 35
+        Some("preimage") => {
+            let (Some(from), Some(to), Some(start_index)) = (args.get(2), args.get(3), args.get(4)) else {
+                return Err(std::io::Error::other("Expected preimage <from> <to> <start> [end] :("));
+            };
+            let start_index = start_index.parse::<u64>().map_err(std::io::Error::other)?;
+            let end_index = match args.get(5) {
+                Some(end_index) => end_index.parse::<u64>().map_err(std::io::Error::other)?,
+                None => start_index,
+            };
 
-        if i == 0 {
-            
-            let _ =match type_run {
-                TypeRun::FirstPart => seed_data_part_1.init_seed_vector(&line_str),
-                TypeRun::SecondPart => seed_data_part_2.init_seed_vector(&line_str),
+            let ranges = almanac.get_preimage(from, to, &Range::new(start_index, end_index))
+                .map_err(almanac_error)?;
+            for range in ranges {
+                println!("{from} {} to {}", range.start_index, range.end_index);
+            }
+        },
+        // Path of a seed or seed range down to the location, with the map range
+        // or identity gap converting each piece, e.g. trace 79 92 --json
+        Some("trace") => {
+            let json = args.iter().any(|arg| arg == "--json");
+            let values: Vec<&String> = args.iter().skip(2).filter(|arg| *arg != "--json").collect();
+
+            let Some(start_index) = values.first() else {
+                return Err(std::io::Error::other("Expected trace <start> [end] [--json] :("));
+            };
+            let start_index = start_index.parse::<u64>().map_err(std::io::Error::other)?;
+            let end_index = match values.get(1) {
+                Some(end_index) => end_index.parse::<u64>().map_err(std::io::Error::other)?,
+                None => start_index,
             };
-            continue;
-        }
-
-        if in_data_state_flag && line_str.is_empty(){
-            in_data_state_flag = false;
-            continue;
-        }
-
-        if !in_data_state_flag {
-
-            for (key,val) in &map_text_to_map_type {
-                if line_str.contains(key) {
-                    in_data_state_flag = true;
-                    current_map_to_fill = val;
-                    break;
-                }
+            if end_index < start_index {
+                return Err(std::io::Error::other("The end of the range is before its start :("));
             }
 
-            if in_data_state_flag {continue};
-        }
-
-        if in_data_state_flag {
-            let _ =match type_run {
-                TypeRun::FirstPart => seed_data_part_1.populate_map_based_on_type(current_map_to_fill, &line_str),
-                TypeRun::SecondPart => seed_data_part_2.populate_map_based_on_type(current_map_to_fill, &line_str),
+            let trace = get_trace(&almanac, "seed", "location", &Range::new(start_index, end_index))
+                .map_err(almanac_error)?;
+            print!("{}", if json {trace_to_json(&trace)} else {trace_to_table(&trace)});
+        },
+        // Compare the linear scan against the binary search on a generated almanac
+        Some("bench") => {
+            let ranges_per_stage = match args.get(2) {
+                Some(ranges_per_stage) => ranges_per_stage.parse::<usize>().map_err(std::io::Error::other)?,
+                None => 20_000,
             };
-        }
+
+            let result = run_lookup_benchmark(ranges_per_stage, 70_000).map_err(std::io::Error::other)?;
+            println!("Stages: {}, ranges per stage: {}, lookups: {}", result.stages, result.ranges_per_stage, result.lookups);
+            println!("Linear scan: {:?}", result.linear_time);
+            println!("Binary search: {:?}", result.binary_search_time);
+        },
+        // Part Two searching backwards from the locations
+        Some("reverse") => {
+            let min_location = almanac.min_location_reverse(&almanac.get_seed_ranges())
+                .map_err(almanac_error)?;
+
+            match min_location {
+                Some(min_location) => println!("The lowest location number found backwards is {min_location}"),
+                None => println!("No location is reached by the seeds"),
+            }
+        },
+        _ => {
+            algorithm(&almanac, TypeRun::FirstPart)?;
+            algorithm(&almanac, TypeRun::SecondPart)?;
+        },
     }
 
-    let mut _min_location: u64 = MAX;
+    Ok(())
+}
 
-    match type_run {
-        TypeRun::FirstPart => _min_location = seed_data_part_1.get_minimum_location(),
-        TypeRun::SecondPart => _min_location = seed_data_part_2.get_minimum_location(),
-    }
-    
+fn almanac_error(err: SeedsDBError) -> std::io::Error
+{
+    std::io::Error::other(format!("{err} :("))
+}
+
+fn algorithm(almanac: &Almanac, type_run: TypeRun) -> std::io::Result<()>
+{
+    // A single seed is just a range of length 1
+    let seed_ranges = match type_run {
+        TypeRun::FirstPart => almanac.get_seed_values(),
+        TypeRun::SecondPart => almanac.get_seed_ranges(),
+    };
+
+    let min_location = almanac.min_location_for(&seed_ranges)
+        .map_err(almanac_error)?;
 
-    println!("The lowest location number that corresponds to initial seed for {:?} is {_min_location}", type_run);
+    println!("The lowest location number that corresponds to initial seed for {:?} is {min_location}", type_run);
 
     Ok(())
 }
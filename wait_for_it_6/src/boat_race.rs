@@ -0,0 +1,8 @@
+pub mod analytics;
+pub mod big_uint;
+pub mod physics;
+pub mod sheet;
+pub mod solver;
+
+#[cfg(test)]
+mod generator;
@@ -0,0 +1,105 @@
+use crate::boat_race::physics::{BoatPhysics, get_number_of_ways_with, search_winning_interval};
+
+#[derive(Debug)]
+pub struct RaceReport {
+    pub time: u64,
+    pub distance_to_beat: u128,
+    // First and last hold times reaching the maximum distance
+    pub optimal_holds: (u64, u64),
+    pub max_distance: u128,
+    pub winning_interval: Option<(u64, u64)>,
+    pub number_of_ways: u128,
+    // How much farther than the record the best hold goes, None if it can not be beaten
+    pub margin: Option<u128>,
+}
+
+pub fn get_race_report<P: BoatPhysics + ?Sized>(physics: &P, time: u64, distance_to_beat: u128) -> RaceReport
+{
+    let max_distance = physics.distance(physics.optimal_hold(time), time);
+
+    // The hold times beating max_distance - 1 are the ones reaching the maximum
+    let optimal_holds = match max_distance.checked_sub(1) {
+        Some(below_max) => search_winning_interval(physics, time, below_max).unwrap_or((0, time)),
+        None => (0, time),
+    };
+
+    RaceReport {
+        time,
+        distance_to_beat,
+        optimal_holds,
+        max_distance,
+        winning_interval: physics.winning_interval(time, distance_to_beat),
+        number_of_ways: get_number_of_ways_with(physics, time, distance_to_beat),
+        margin: max_distance.checked_sub(distance_to_beat).filter(|margin| *margin > 0),
+    }
+}
+
+// Races up to this long fit a row per hold time
+pub const MAX_PLOT_TIME: u64 = 100;
+
+// A row per hold time with its distance and a bar scaled to `width`, winning holds
+// marked with '*' and the record drawn as a '|' across the bars
+pub fn race_to_plot<P: BoatPhysics + ?Sized>(physics: &P, time: u64, distance_to_beat: u128, width: usize) -> String
+{
+    let distances: Vec<u128> = (0 ..=time).map(|hold| physics.distance(hold, time)).collect();
+    let scale = distances.iter().copied().max().unwrap_or(0).max(distance_to_beat).max(1);
+    let bar_len = |distance: u128| (distance.saturating_mul(width as u128) / scale) as usize;
+
+    let record_column = bar_len(distance_to_beat);
+    let distance_width = distances.iter().map(|distance| distance.to_string().len()).max().unwrap_or(1).max("distance".len());
+
+    let mut result = format!("hold {:>distance_width$}\n", "distance");
+    for (hold, distance) in distances.iter().enumerate() {
+        let mut bar: Vec<char> = vec![' '; width + 1];
+        bar.iter_mut().take(bar_len(*distance)).for_each(|cell| *cell = '#');
+        bar[record_column] = '|';
+
+        let mark = if *distance > distance_to_beat {'*'} else {' '};
+        let line = format!("{hold:>4} {distance:>distance_width$} {mark} {}", bar.iter().collect::<String>());
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boat_race::physics::ClassicBoat;
+
+    #[test]
+    fn example_race_report() {
+        let report = get_race_report(&ClassicBoat, 7, 9);
+
+        assert_eq!(report.winning_interval, Some((2, 5)));
+        assert_eq!(report.number_of_ways, 4);
+        assert_eq!(report.optimal_holds, (3, 4));
+        assert_eq!(report.max_distance, 12);
+        assert_eq!(report.margin, Some(3));
+    }
+
+    #[test]
+    fn record_that_can_not_be_beaten() {
+        let report = get_race_report(&ClassicBoat, 7, 12);
+
+        assert_eq!(report.winning_interval, None);
+        assert_eq!(report.number_of_ways, 0);
+        assert_eq!(report.margin, None);
+    }
+
+    #[test]
+    fn example_race_plot() {
+        assert_eq!(race_to_plot(&ClassicBoat, 7, 9, 12), "\
+hold distance
+   0        0            |
+   1        6   ######   |
+   2       10 * #########|
+   3       12 * #########|##
+   4       12 * #########|##
+   5       10 * #########|
+   6        6   ######   |
+   7        0            |
+");
+    }
+}
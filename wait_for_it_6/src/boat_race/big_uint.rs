@@ -0,0 +1,286 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+// Unsigned integer of any size, enough for races whose numbers do not fit in a u128.
+// Little-endian u32 limbs without trailing zeros, so zero has no limbs at all.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+
+    pub fn zero() -> Self
+    {
+        BigUint { limbs: Vec::new() }
+    }
+
+    pub fn from_u128(mut value: u128) -> Self
+    {
+        let mut limbs: Vec<u32> = Vec::new();
+        while value > 0 {
+            limbs.push(value as u32);
+            value >>= 32;
+        }
+
+        BigUint { limbs }
+    }
+
+    pub fn from_u64(value: u64) -> Self
+    {
+        BigUint::from_u128(value as u128)
+    }
+
+    // Only ascii digits are accepted, an empty string is not a number
+    pub fn from_decimal_str(digits: &str) -> Option<Self>
+    {
+        if digits.is_empty() {
+            return None;
+        }
+
+        let mut result = BigUint::zero();
+        for digit in digits.chars() {
+            let digit = digit.to_digit(10)?;
+            result.mul_add_small(10, digit);
+        }
+
+        Some(result)
+    }
+
+    pub fn to_u128(&self) -> Option<u128>
+    {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+
+        Some(self.limbs.iter().rev().fold(0, |value, limb| (value << 32) | *limb as u128))
+    }
+
+    pub fn to_u64(&self) -> Option<u64>
+    {
+        self.to_u128().and_then(|value| u64::try_from(value).ok())
+    }
+
+    pub fn is_zero(&self) -> bool
+    {
+        self.limbs.is_empty()
+    }
+
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint>
+    {
+        if *self < *other {
+            return None;
+        }
+
+        let mut limbs: Vec<u32> = Vec::with_capacity(self.limbs.len());
+        let mut borrow: i64 = 0;
+
+        for (i, limb) in self.limbs.iter().enumerate() {
+            let mut difference = *limb as i64 - other.limbs.get(i).copied().unwrap_or(0) as i64 - borrow;
+            borrow = 0;
+            if difference < 0 {
+                difference += 1 << 32;
+                borrow = 1;
+            }
+            limbs.push(difference as u32);
+        }
+
+        Some(BigUint { limbs }.normalized())
+    }
+
+    // Integer division by 2
+    pub fn half(&self) -> BigUint
+    {
+        let mut limbs = self.limbs.clone();
+        let mut carry: u32 = 0;
+
+        for limb in limbs.iter_mut().rev() {
+            let next_carry = *limb & 1;
+            *limb = (*limb >> 1) | (carry << 31);
+            carry = next_carry;
+        }
+
+        BigUint { limbs }.normalized()
+    }
+
+    fn mul_add_small(&mut self, factor: u32, addend: u32)
+    {
+        let mut carry = addend as u64;
+        for limb in self.limbs.iter_mut() {
+            let value = *limb as u64 * factor as u64 + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+        self.normalize();
+    }
+
+    // Returns the remainder
+    fn div_small(&mut self, divisor: u32) -> u32
+    {
+        let mut remainder: u64 = 0;
+        for limb in self.limbs.iter_mut().rev() {
+            let value = (remainder << 32) | *limb as u64;
+            *limb = (value / divisor as u64) as u32;
+            remainder = value % divisor as u64;
+        }
+
+        self.normalize();
+        remainder as u32
+    }
+
+    fn normalize(&mut self)
+    {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    fn normalized(mut self) -> Self
+    {
+        self.normalize();
+        self
+    }
+}
+
+impl Ord for BigUint {
+
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs.len().cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut limbs: Vec<u32> = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry: u64 = 0;
+
+        for i in 0 .. self.limbs.len().max(other.limbs.len()) {
+            let value = self.limbs.get(i).copied().unwrap_or(0) as u64
+                + other.limbs.get(i).copied().unwrap_or(0) as u64 + carry;
+            limbs.push(value as u32);
+            carry = value >> 32;
+        }
+        limbs.push(carry as u32);
+
+        BigUint { limbs }.normalized()
+    }
+}
+
+// Panics when the result would be negative, like the integer types do in debug builds
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other).expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs: Vec<u32> = vec![0; self.limbs.len() + other.limbs.len()];
+
+        for (i, limb_a) in self.limbs.iter().enumerate() {
+            let mut carry: u64 = 0;
+            for (j, limb_b) in other.limbs.iter().enumerate() {
+                let value = *limb_a as u64 * *limb_b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = value as u32;
+                carry = value >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        BigUint { limbs }.normalized()
+    }
+}
+
+impl fmt::Display for BigUint {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        // Chunks of 9 digits, from the lowest to the highest
+        let mut value = self.clone();
+        let mut chunks: Vec<u32> = Vec::new();
+        while !value.is_zero() {
+            chunks.push(value.div_small(1_000_000_000));
+        }
+
+        let mut chunks = chunks.iter().rev();
+        if let Some(highest) = chunks.next() {
+            write!(f, "{highest}")?;
+        }
+        for chunk in chunks {
+            write!(f, "{chunk:09}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boat_race::generator::Generator;
+
+    // Values that fit in a u128 have to give the same results as u128
+    #[test]
+    fn arithmetic_matches_u128() {
+        let mut generator = Generator::new(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0 .. 1_000 {
+            let a = (generator.next_u64() as u128) << 32 | generator.next_u64() as u128;
+            let b = generator.next_u64() as u128;
+            let (big_a, big_b) = (BigUint::from_u128(a), BigUint::from_u128(b));
+
+            assert_eq!((&big_a + &big_b).to_u128(), a.checked_add(b), "{a} + {b}");
+            assert_eq!((&big_a * &big_b).to_u128(), a.checked_mul(b), "{a} * {b}");
+            assert_eq!(big_a.checked_sub(&big_b).and_then(|value| value.to_u128()), a.checked_sub(b), "{a} - {b}");
+            assert_eq!(big_a.half().to_u128(), Some(a / 2), "{a} / 2");
+            assert_eq!(big_a.to_string(), a.to_string());
+            assert_eq!(BigUint::from_decimal_str(&a.to_string()), Some(big_a.clone()));
+            assert_eq!(big_a.cmp(&big_b), a.cmp(&b), "{a} cmp {b}");
+        }
+    }
+
+    #[test]
+    fn values_past_u128() {
+        let max = BigUint::from_u128(u128::MAX);
+        let one = BigUint::from_u64(1);
+        let past_max = &max + &one;
+
+        assert_eq!(past_max.to_string(), "340282366920938463463374607431768211456");
+        assert_eq!(past_max.to_u128(), None);
+        assert_eq!(&past_max - &one, max);
+        assert_eq!((&max * &max).to_string(),
+            "115792089237316195423570985008687907852589419931798687112530834793049593217025");
+        assert!(past_max > max);
+    }
+
+    #[test]
+    fn decimal_strings() {
+        assert_eq!(BigUint::from_decimal_str(""), None);
+        assert_eq!(BigUint::from_decimal_str("12a"), None);
+        assert_eq!(BigUint::from_decimal_str("000"), Some(BigUint::zero()));
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(BigUint::from_decimal_str("1000000000000000000000").unwrap().to_string(), "1000000000000000000000");
+    }
+}
@@ -0,0 +1,17 @@
+// xorshift64, reproducible pseudo random races for the tests
+pub struct Generator(u64);
+
+impl Generator {
+
+    // A zero state would stay zero forever
+    pub fn new(seed: u64) -> Self {
+        Generator(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
@@ -0,0 +1,234 @@
+use crate::boat_race::solver::{calculate_distance, winning_interval};
+
+// How far a boat goes holding the button some ms of a race. The distance has to be
+// unimodal in the hold time: rising up to optimal_hold and falling after it, strictly
+// wherever it is positive (two equal peaks are fine).
+pub trait BoatPhysics {
+
+    fn distance(&self, hold: u64, time: u64) -> u128;
+
+    // A hold time giving the maximum distance
+    fn optimal_hold(&self, time: u64) -> u64;
+
+    // First and last winning hold times, None if the record can not be beaten.
+    // Models with a closed form override it, the rest use the search.
+    fn winning_interval(&self, time: u64, distance_to_beat: u128) -> Option<(u64, u64)>
+    {
+        search_winning_interval(self, time, distance_to_beat)
+    }
+}
+
+pub fn get_number_of_ways_with<P: BoatPhysics + ?Sized>(physics: &P, time: u64, distance_to_beat: u128) -> u128
+{
+    physics.winning_interval(time, distance_to_beat).map_or(0, |(lower, upper)| (upper - lower) as u128 + 1)
+}
+
+// Binary search on both sides of the optimal hold time, where the distance is monotone
+pub fn search_winning_interval<P: BoatPhysics + ?Sized>(physics: &P, time: u64, distance_to_beat: u128) -> Option<(u64, u64)>
+{
+    let optimal_hold = physics.optimal_hold(time);
+    if physics.distance(optimal_hold, time) <= distance_to_beat {
+        return None;
+    }
+
+    let (mut low_time, mut high_time) = (0, optimal_hold);
+    while low_time < high_time {
+        let mid_time = low_time + (high_time - low_time) / 2;
+
+        if physics.distance(mid_time, time) > distance_to_beat {
+            high_time = mid_time;
+        }
+        else {
+            low_time = mid_time + 1;
+        }
+    }
+    let lower = low_time;
+
+    let (mut low_time, mut high_time) = (optimal_hold, time);
+    while low_time < high_time {
+        let mid_time = low_time + (high_time - low_time).div_ceil(2);
+
+        if physics.distance(mid_time, time) > distance_to_beat {
+            low_time = mid_time;
+        }
+        else {
+            high_time = mid_time - 1;
+        }
+    }
+
+    Some((lower, low_time))
+}
+
+// The puzzle boat: 1 mm/ms of speed per ms held, no limits
+pub struct ClassicBoat;
+
+impl BoatPhysics for ClassicBoat {
+
+    fn distance(&self, hold: u64, time: u64) -> u128
+    {
+        calculate_distance(hold, time)
+    }
+
+    fn optimal_hold(&self, time: u64) -> u64
+    {
+        time / 2
+    }
+
+    fn winning_interval(&self, time: u64, distance_to_beat: u128) -> Option<(u64, u64)>
+    {
+        winning_interval(time, distance_to_beat)
+    }
+}
+
+// The first charge_delay ms held do nothing, then every ms adds acceleration mm/ms
+// of speed up to max_speed. Once released, the boat loses drag mm/ms of speed
+// every ms until it stops.
+#[derive(Clone, Debug)]
+pub struct ConfigurableBoat {
+    pub acceleration: u64,
+    pub max_speed: Option<u64>,
+    pub drag: u64,
+    pub charge_delay: u64,
+}
+
+impl ConfigurableBoat {
+
+    pub fn speed(&self, hold: u64) -> u128
+    {
+        let speed = hold.saturating_sub(self.charge_delay) as u128 * self.acceleration as u128;
+
+        self.max_speed.map_or(speed, |max_speed| speed.min(max_speed as u128))
+    }
+
+    // Without a cap or drag the distance is acceleration * (h - delay) * (T - h), the
+    // puzzle race shifted by the delay, and acceleration * k beats D when k beats D / acceleration
+    fn has_closed_form(&self) -> bool
+    {
+        self.max_speed.is_none() && self.drag == 0 && self.acceleration > 0
+    }
+}
+
+impl BoatPhysics for ConfigurableBoat {
+
+    fn distance(&self, hold: u64, time: u64) -> u128
+    {
+        let speed = self.speed(hold);
+        let travel_time = time.saturating_sub(hold) as u128;
+
+        if self.drag == 0 {
+            return speed.saturating_mul(travel_time);
+        }
+
+        // ms with some speed left, the speeds form an arithmetic series
+        let drag = self.drag as u128;
+        let moving_time = travel_time.min(speed.div_ceil(drag));
+
+        moving_time.saturating_mul(speed)
+            .saturating_sub(drag.saturating_mul(moving_time * moving_time.saturating_sub(1) / 2))
+    }
+
+    fn optimal_hold(&self, time: u64) -> u64
+    {
+        if self.has_closed_form() {
+            return self.charge_delay.min(time) + time.saturating_sub(self.charge_delay) / 2;
+        }
+
+        // Only holding past the delay and releasing before the end can move the boat,
+        // there the distance rises strictly and then falls strictly
+        let (mut low_time, mut high_time) = (self.charge_delay.saturating_add(1), time.saturating_sub(1));
+        if low_time > high_time {
+            return 0;
+        }
+
+        while low_time < high_time {
+            let mid_time = low_time + (high_time - low_time) / 2;
+
+            if self.distance(mid_time, time) < self.distance(mid_time + 1, time) {
+                low_time = mid_time + 1;
+            }
+            else {
+                high_time = mid_time;
+            }
+        }
+
+        low_time
+    }
+
+    fn winning_interval(&self, time: u64, distance_to_beat: u128) -> Option<(u64, u64)>
+    {
+        if !self.has_closed_form() {
+            return search_winning_interval(self, time, distance_to_beat);
+        }
+
+        let shortened_time = time.checked_sub(self.charge_delay)?;
+        let (lower, upper) = winning_interval(shortened_time, distance_to_beat / self.acceleration as u128)?;
+
+        Some((lower + self.charge_delay, upper + self.charge_delay))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boat_race::analytics::get_race_report;
+
+    // Every model has to give, with its closed form or the search, the same interval as
+    // trying every hold time, and that interval must hold every winning hold time.
+    // The best hold times of the report have to be the ones found trying them all.
+    fn check_boat<P: BoatPhysics>(boat: &P, name: &str) {
+        for time in 0 ..=40 {
+            let distances: Vec<u128> = (0 ..=time).map(|hold| boat.distance(hold, time)).collect();
+            let best = distances.iter().copied().max().unwrap_or(0);
+
+            assert_eq!(distances[boat.optimal_hold(time) as usize], best,
+                "{name}: hold {} is not the best one for a race of {time} ms", boat.optimal_hold(time));
+
+            let best_holds: Vec<u64> = (0 ..=time).filter(|hold| distances[*hold as usize] == best).collect();
+            let optimal_holds = get_race_report(boat, time, 0).optimal_holds;
+            assert_eq!((Some(&optimal_holds.0), Some(&optimal_holds.1)), (best_holds.first(), best_holds.last()),
+                "{name}: best holds for a race of {time} ms");
+
+            for distance_to_beat in 0 ..=best + 1 {
+                let winning: Vec<u64> = (0 ..=time).filter(|hold| distances[*hold as usize] > distance_to_beat).collect();
+                let brute_force = winning.first().map(|lower| (*lower, *winning.last().unwrap_or(lower)));
+
+                assert!(brute_force.is_none_or(|(lower, upper)| (upper - lower + 1) as usize == winning.len()),
+                    "{name}, race of {time} ms with record {distance_to_beat}: winning holds {winning:?} have gaps");
+                assert_eq!(boat.winning_interval(time, distance_to_beat), brute_force,
+                    "{name}, race of {time} ms with record {distance_to_beat}");
+                assert_eq!(search_winning_interval(boat, time, distance_to_beat), brute_force,
+                    "{name}, race of {time} ms with record {distance_to_beat}");
+            }
+        }
+    }
+
+    #[test]
+    fn classic_boat() {
+        check_boat(&ClassicBoat, "classic boat");
+    }
+
+    #[test]
+    fn configurable_boats_with_closed_form() {
+        for boat in [
+            ConfigurableBoat { acceleration: 1, max_speed: None, drag: 0, charge_delay: 0 },
+            ConfigurableBoat { acceleration: 3, max_speed: None, drag: 0, charge_delay: 4 },
+        ] {
+            assert!(boat.has_closed_form());
+            check_boat(&boat, &format!("{boat:?}"));
+        }
+    }
+
+    #[test]
+    fn configurable_boats_with_search() {
+        for boat in [
+            ConfigurableBoat { acceleration: 2, max_speed: Some(9), drag: 0, charge_delay: 1 },
+            ConfigurableBoat { acceleration: 2, max_speed: None, drag: 1, charge_delay: 0 },
+            ConfigurableBoat { acceleration: 5, max_speed: Some(20), drag: 3, charge_delay: 2 },
+            ConfigurableBoat { acceleration: 1, max_speed: Some(4), drag: 2, charge_delay: 7 },
+            ConfigurableBoat { acceleration: 0, max_speed: None, drag: 0, charge_delay: 0 },
+        ] {
+            assert!(!boat.has_closed_form());
+            check_boat(&boat, &format!("{boat:?}"));
+        }
+    }
+}
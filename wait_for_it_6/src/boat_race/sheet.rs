@@ -0,0 +1,223 @@
+use std::fmt;
+use std::io::BufRead;
+
+use crate::boat_race::big_uint::BigUint;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SheetRow {
+    Time,
+    Distance,
+}
+
+impl fmt::Display for SheetRow {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SheetRow::Time => write!(f, "Time"),
+            SheetRow::Distance => write!(f, "Distance"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SheetError {
+    Io(std::io::Error),
+    MissingSeparator { line: usize },
+    UnknownHeader { line: usize, header: String },
+    DuplicateRow { line: usize, row: SheetRow },
+    EmptyRow { line: usize, row: SheetRow },
+    InvalidNumber { line: usize, token: String },
+    MissingRow { sheet: usize, row: SheetRow },
+    ColumnMismatch { sheet: usize, times: usize, distances: usize },
+    NoSheets,
+}
+
+impl fmt::Display for SheetError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SheetError::Io(err) => write!(f, "could not read the race sheet: {err}"),
+            SheetError::MissingSeparator { line } => write!(f, "line {line}: expected '<header>: <numbers>'"),
+            SheetError::UnknownHeader { line, header } =>
+                write!(f, "line {line}: '{header}' is not a header, only Time and Distance are"),
+            SheetError::DuplicateRow { line, row } => write!(f, "line {line}: the sheet already has a {row} row"),
+            SheetError::EmptyRow { line, row } => write!(f, "line {line}: the {row} row has no numbers"),
+            SheetError::InvalidNumber { line, token } => write!(f, "line {line}: '{token}' is not a number"),
+            SheetError::MissingRow { sheet, row } => write!(f, "sheet {sheet} has no {row} row"),
+            SheetError::ColumnMismatch { sheet, times, distances } =>
+                write!(f, "sheet {sheet} has {times} times but {distances} distances"),
+            SheetError::NoSheets => write!(f, "there are no races in the sheet"),
+        }
+    }
+}
+
+impl std::error::Error for SheetError {}
+
+impl From<std::io::Error> for SheetError {
+
+    fn from(err: std::io::Error) -> SheetError {
+        SheetError::Io(err)
+    }
+}
+
+// Numbers of one race sheet as written, so they can be read as separate races
+// (Part One) or with their digits joined into a single race (Part Two)
+#[derive(Debug)]
+pub struct RaceSheet {
+    pub times: Vec<String>,
+    pub distances: Vec<String>,
+}
+
+impl RaceSheet {
+
+    pub fn get_races(&self) -> Vec<(BigUint, BigUint)>
+    {
+        // Only digits got through parse_sheets
+        let to_number = |token: &String| BigUint::from_decimal_str(token).unwrap_or_else(BigUint::zero);
+
+        self.times.iter().map(to_number).zip(self.distances.iter().map(to_number)).collect()
+    }
+
+    pub fn get_joined_race(&self) -> (BigUint, BigUint)
+    {
+        let join = |tokens: &[String]| BigUint::from_decimal_str(&tokens.concat()).unwrap_or_else(BigUint::zero);
+
+        (join(&self.times), join(&self.distances))
+    }
+}
+
+// Reads every sheet of the file: a Time row and a Distance row, in any order, with
+// any spacing and case in the headers. Once a sheet has both rows the next header
+// starts a new sheet, blank lines are ignored.
+pub fn parse_sheets<R: BufRead>(reader: R) -> Result<Vec<RaceSheet>, SheetError>
+{
+    let mut sheets: Vec<RaceSheet> = Vec::new();
+    let mut times: Option<Vec<String>> = None;
+    let mut distances: Option<Vec<String>> = None;
+
+    for (i, line) in reader.lines().enumerate() {
+
+        let line_str = line?;
+        let line = i + 1;
+
+        if line_str.trim().is_empty() {
+            continue;
+        }
+
+        let Some((header, numbers_str)) = line_str.split_once(':') else {
+            return Err(SheetError::MissingSeparator { line });
+        };
+
+        let row = match header.trim().to_ascii_lowercase().as_str() {
+            "time" => SheetRow::Time,
+            "distance" => SheetRow::Distance,
+            _ => return Err(SheetError::UnknownHeader { line, header: header.trim().to_string() }),
+        };
+
+        if times.is_some() && distances.is_some() {
+            close_sheet(&mut sheets, &mut times, &mut distances)?;
+        }
+
+        let numbers: Vec<String> = numbers_str.split_whitespace().map(|token| token.to_string()).collect();
+        if let Some(token) = numbers.iter().find(|token| !token.chars().all(|c| c.is_ascii_digit())) {
+            return Err(SheetError::InvalidNumber { line, token: token.clone() });
+        }
+        if numbers.is_empty() {
+            return Err(SheetError::EmptyRow { line, row });
+        }
+
+        let slot = match row {
+            SheetRow::Time => &mut times,
+            SheetRow::Distance => &mut distances,
+        };
+        if slot.is_some() {
+            return Err(SheetError::DuplicateRow { line, row });
+        }
+        *slot = Some(numbers);
+    }
+
+    if times.is_some() || distances.is_some() {
+        close_sheet(&mut sheets, &mut times, &mut distances)?;
+    }
+
+    if sheets.is_empty() {
+        return Err(SheetError::NoSheets);
+    }
+
+    Ok(sheets)
+}
+
+fn close_sheet(sheets: &mut Vec<RaceSheet>, times: &mut Option<Vec<String>>,
+        distances: &mut Option<Vec<String>>) -> Result<(), SheetError>
+{
+    let sheet = sheets.len() + 1;
+
+    let times = times.take().ok_or(SheetError::MissingRow { sheet, row: SheetRow::Time })?;
+    let distances = distances.take().ok_or(SheetError::MissingRow { sheet, row: SheetRow::Distance })?;
+
+    if times.len() != distances.len() {
+        return Err(SheetError::ColumnMismatch { sheet, times: times.len(), distances: distances.len() });
+    }
+
+    sheets.push(RaceSheet { times, distances });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(sheet: &[u8]) -> String {
+        match parse_sheets(sheet) {
+            Ok(sheets) => panic!("{:?} was read as {sheets:?}", String::from_utf8_lossy(sheet)),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn broken_sheets() {
+        assert_eq!(parse_error(b""), "there are no races in the sheet");
+        assert_eq!(parse_error(b"Time: 7 15\nDistance: 9\n"), "sheet 1 has 2 times but 1 distances");
+        assert_eq!(parse_error(b"Time: 7\nDistance: 9\nTime: 15\n"), "sheet 2 has no Distance row");
+        assert_eq!(parse_error(b"Distance: 9\nDistance: 40\n"), "line 2: the sheet already has a Distance row");
+        assert_eq!(parse_error(b"Time: 7 x\nDistance: 9 40\n"), "line 1: 'x' is not a number");
+        assert_eq!(parse_error(b"Time: 7 -15\nDistance: 9 40\n"), "line 1: '-15' is not a number");
+        assert_eq!(parse_error(b"Time:\nDistance: 9\n"), "line 1: the Time row has no numbers");
+        assert_eq!(parse_error(b"Time: 7\nSpeed: 9\n"), "line 2: 'Speed' is not a header, only Time and Distance are");
+        assert_eq!(parse_error(b"Time 7\n"), "line 1: expected '<header>: <numbers>'");
+        assert_eq!(parse_error(b"Time: 7\n\xff\n"), "could not read the race sheet: stream did not contain valid UTF-8");
+    }
+
+    // Spacing, case and order of the rows do not change the races
+    #[test]
+    fn equivalent_sheets() {
+        let sheets: [&[u8]; 3] = [
+            b"Time:      7  15   30\nDistance:  9  40  200\n",
+            b"\n  distance :9 40 200\n\nTIME:7    15 30   \n\n",
+            b"Distance:\t9\t40\t200\nTime: 7 15 30",
+        ];
+
+        for sheet in sheets {
+            let sheets = parse_sheets(sheet).unwrap();
+            assert_eq!(sheets.len(), 1);
+
+            let races: Vec<(String, String)> = sheets[0].get_races().iter()
+                .map(|(time, distance)| (time.to_string(), distance.to_string()))
+                .collect();
+            assert_eq!(races, [("7", "9"), ("15", "40"), ("30", "200")].map(|(time, distance)| (time.to_string(), distance.to_string())));
+
+            let (joined_time, joined_distance) = sheets[0].get_joined_race();
+            assert_eq!((joined_time.to_string(), joined_distance.to_string()), ("71530".to_string(), "940200".to_string()));
+        }
+    }
+
+    #[test]
+    fn several_sheets() {
+        let sheets = parse_sheets(&b"Time: 7\nDistance: 9\nDistance: 40\nTime: 15\n\nTime: 30\nDistance: 200\n"[..]).unwrap();
+
+        assert_eq!(sheets.len(), 3);
+        assert_eq!((&sheets[1].times[..], &sheets[1].distances[..]), (&["15".to_string()][..], &["40".to_string()][..]));
+        assert_eq!((&sheets[2].times[..], &sheets[2].distances[..]), (&["30".to_string()][..], &["200".to_string()][..]));
+    }
+}
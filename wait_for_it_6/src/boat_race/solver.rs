@@ -0,0 +1,253 @@
+// Holding the button h ms in a race of T ms travels h * (T - h) mm, so beating the
+// record D means h^2 - T*h + D < 0: every hold time strictly between the roots
+// (T - sqrt(T^2 - 4D)) / 2 and (T + sqrt(T^2 - 4D)) / 2 wins.
+
+use crate::boat_race::big_uint::BigUint;
+
+pub fn calculate_distance(time_pressed_button: u64, max_time: u64) -> u128
+{
+    (max_time - time_pressed_button) as u128 * time_pressed_button as u128
+}
+
+// Exact first and last winning hold times, None if the record can not be beaten.
+// The integer square root is exact, so the candidate is at most one step off the
+// real root and the two loops only correct the rounding of the division.
+// A time up to u64::MAX squared fits in a u128, a record too big to multiply by 4
+// is past the best distance T^2 / 4 and can not be beaten.
+pub fn winning_interval(time: u64, distance_to_beat: u128) -> Option<(u64, u64)>
+{
+    let (time_wide, distance_wide) = (time as u128, distance_to_beat);
+
+    let discriminant = (time_wide * time_wide).checked_sub(distance_wide.checked_mul(4)?)?;
+    let root = discriminant.isqrt();
+
+    let half_time = time / 2;
+    let mut lower = ((time_wide - root) / 2) as u64;
+
+    while lower <= half_time && calculate_distance(lower, time) <= distance_wide {
+        lower += 1;
+    }
+    while lower > 0 && calculate_distance(lower - 1, time) > distance_wide {
+        lower -= 1;
+    }
+
+    if lower > half_time {
+        return None;
+    }
+
+    // The distance is symmetric around T / 2
+    Some((lower, time - lower))
+}
+
+pub fn get_number_of_ways(time: u64, distance_to_beat: u128) -> u64
+{
+    winning_interval(time, distance_to_beat).map_or(0, |(lower, upper)| upper - lower + 1)
+}
+
+// Closed form when the time fits in a u64 and the record in a u128, search on big
+// numbers otherwise. Can be more than u64::MAX when the time is u64::MAX itself.
+pub fn get_number_of_ways_big(time: &BigUint, distance_to_beat: &BigUint) -> BigUint
+{
+    if let (Some(time), Some(distance_to_beat)) = (time.to_u64(), distance_to_beat.to_u128()) {
+        return match winning_interval(time, distance_to_beat) {
+            Some((lower, upper)) => BigUint::from_u128((upper - lower) as u128 + 1),
+            None => BigUint::zero(),
+        };
+    }
+
+    match winning_interval_big(time, distance_to_beat) {
+        Some((lower, upper)) => &(&upper - &lower) + &BigUint::from_u64(1),
+        None => BigUint::zero(),
+    }
+}
+
+pub fn calculate_distance_big(time_pressed_button: &BigUint, max_time: &BigUint) -> BigUint
+{
+    &(max_time - time_pressed_button) * time_pressed_button
+}
+
+// Binary search of the first winning hold time up to T / 2, as the square root
+// is not available on big numbers
+pub fn winning_interval_big(time: &BigUint, distance_to_beat: &BigUint) -> Option<(BigUint, BigUint)>
+{
+    let one = BigUint::from_u64(1);
+    let mut low_time = BigUint::zero();
+    let mut high_time = time.half();
+
+    if calculate_distance_big(&high_time, time) <= *distance_to_beat {
+        return None;
+    }
+
+    while low_time < high_time {
+        let mid_time = &low_time + &(&high_time - &low_time).half();
+
+        if calculate_distance_big(&mid_time, time) > *distance_to_beat {
+            high_time = mid_time;
+        }
+        else {
+            low_time = &mid_time + &one;
+        }
+    }
+
+    let upper = time - &low_time;
+    Some((low_time, upper))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use super::*;
+    use crate::boat_race::generator::Generator;
+    use crate::boat_race::sheet::parse_sheets;
+
+    // Previous solution, searching the first winning hold time up to the fastest one
+    // at T / 2 where the distance is increasing
+    fn winning_interval_binary_search(time: u64, distance_to_beat: u64) -> Option<(u64, u64)> {
+        let target_distance = distance_to_beat as u128;
+        let mut low_time: u64 = 0;
+        let mut high_time: u64 = time / 2;
+
+        if calculate_distance(high_time, time) <= target_distance {
+            return None;
+        }
+
+        while low_time < high_time {
+            let mid_time = low_time + (high_time - low_time) / 2;
+
+            if calculate_distance(mid_time, time) > target_distance {
+                high_time = mid_time;
+            }
+            else {
+                low_time = mid_time + 1;
+            }
+        }
+
+        Some((low_time, time - low_time))
+    }
+
+    fn winning_interval_brute_force(time: u64, distance_to_beat: u64) -> Option<(u64, u64)> {
+        let mut winning = (0 ..=time).filter(|hold| calculate_distance(*hold, time) > distance_to_beat as u128);
+        let lower = winning.next()?;
+        let upper = winning.next_back().unwrap_or(lower);
+
+        Some((lower, upper))
+    }
+
+    // Both parts of every sheet of the puzzle input
+    fn input_races() -> Vec<(u64, u64)> {
+        let input = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/data/input.txt")).unwrap();
+        let mut races: Vec<(u64, u64)> = Vec::new();
+
+        for sheet in parse_sheets(BufReader::new(input)).unwrap() {
+            for (time, distance) in sheet.get_races().iter().chain([&sheet.get_joined_race()]) {
+                races.push((time.to_u64().unwrap(), distance.to_u64().unwrap()));
+            }
+        }
+
+        races
+    }
+
+    fn check_big_race(time: &BigUint, distance_to_beat: &BigUint) {
+        let Some((lower, upper)) = winning_interval_big(time, distance_to_beat) else {
+            assert!(calculate_distance_big(&time.half(), time) <= *distance_to_beat,
+                "race of {time} ms with record {distance_to_beat} can be won holding {}", time.half());
+            return;
+        };
+
+        let wins = |hold: &BigUint| calculate_distance_big(hold, time) > *distance_to_beat;
+        let before_lower = lower.checked_sub(&BigUint::from_u64(1));
+
+        assert!(wins(&lower) && !before_lower.is_some_and(|hold| wins(&hold)) && &lower + &upper == *time,
+            "race of {time} ms with record {distance_to_beat}: wrong interval [{lower}, {upper}]");
+    }
+
+    #[test]
+    fn example_races() {
+        assert_eq!(winning_interval(7, 9), Some((2, 5)));
+        assert_eq!(get_number_of_ways(15, 40), 8);
+        assert_eq!(get_number_of_ways(30, 200), 9);
+        assert_eq!(get_number_of_ways(71530, 940200), 71503);
+    }
+
+    // Every record from 0 to a bit past the best possible distance
+    #[test]
+    fn closed_form_matches_brute_force_and_binary_search() {
+        for time in 0 ..=60 {
+            for distance_to_beat in 0 ..=time * time / 4 + 2 {
+                let closed_form = winning_interval(time, distance_to_beat as u128);
+
+                assert_eq!(closed_form, winning_interval_brute_force(time, distance_to_beat),
+                    "race of {time} ms with record {distance_to_beat}");
+                assert_eq!(closed_form, winning_interval_binary_search(time, distance_to_beat),
+                    "race of {time} ms with record {distance_to_beat}");
+            }
+        }
+    }
+
+    #[test]
+    fn closed_form_matches_binary_search_on_the_input() {
+        for (time, distance_to_beat) in input_races() {
+            assert_eq!(winning_interval(time, distance_to_beat as u128),
+                winning_interval_binary_search(time, distance_to_beat),
+                "race of {time} ms with record {distance_to_beat}");
+        }
+    }
+
+    #[test]
+    fn records_too_big_to_multiply() {
+        assert_eq!(winning_interval(10, u128::MAX), None);
+        assert_eq!(winning_interval(u64::MAX, u128::MAX / 4 + 1), None);
+    }
+
+    #[test]
+    fn big_search_matches_closed_form_near_u64_max() {
+        let mut generator = Generator::new(0x9e37_79b9_7f4a_7c15);
+
+        for i in 0 .. 1_000 {
+            let time = u64::MAX - (i % 7);
+            let best = (time / 2) as u128 * (time - time / 2) as u128;
+            let distance_to_beat = best - (generator.next_u64() as u128 % (best / 2)) * (i as u128 % 3);
+
+            let closed_form = winning_interval(time, distance_to_beat)
+                .map(|(lower, upper)| (Some(lower), Some(upper)));
+            let big_search = winning_interval_big(&BigUint::from_u64(time), &BigUint::from_u128(distance_to_beat))
+                .map(|(lower, upper)| (lower.to_u64(), upper.to_u64()));
+
+            assert_eq!(closed_form, big_search, "race of {time} ms with record {distance_to_beat}");
+        }
+    }
+
+    #[test]
+    fn number_of_ways_past_u64() {
+        let ways = get_number_of_ways_big(&BigUint::from_u64(u64::MAX), &BigUint::zero());
+        assert_eq!(ways.to_u128(), Some(u64::MAX as u128 - 1));
+
+        let time = BigUint::from_decimal_str("100000000000000000000000000000000000000000").unwrap();
+        let ways = get_number_of_ways_big(&time, &BigUint::zero());
+        assert_eq!(ways.to_string(), "99999999999999999999999999999999999999999");
+    }
+
+    // The first winning hold time has to be the first to win on races with hundreds of digits
+    #[test]
+    fn big_search_on_huge_races() {
+        let mut generator = Generator::new(0x2545_f491_4f6c_dd1d);
+
+        for digits in [40, 100, 300] {
+            for _ in 0 .. 10 {
+                let time_digits: String = (0 .. digits).map(|_| char::from(b'1' + (generator.next_u64() % 9) as u8)).collect();
+                let time = BigUint::from_decimal_str(&time_digits).unwrap();
+
+                let best = calculate_distance_big(&time.half(), &time);
+                let below_best = BigUint::from_u128(generator.next_u64() as u128 * generator.next_u64() as u128);
+                let distance_to_beat = best.checked_sub(&below_best).unwrap_or_else(BigUint::zero);
+
+                check_big_race(&time, &distance_to_beat);
+                check_big_race(&time, &best);
+                assert!(get_number_of_ways_big(&time, &best).is_zero(),
+                    "race of {time} ms can not be won against its best distance {best}");
+            }
+        }
+    }
+}
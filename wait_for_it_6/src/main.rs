@@ -56,7 +56,15 @@
 // How many ways can you beat the record in this one much longer race?
 
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufReader;
+
+use crate::boat_race::{analytics::{MAX_PLOT_TIME, get_race_report, race_to_plot},
+                       big_uint::BigUint,
+                       physics::{BoatPhysics, ClassicBoat, ConfigurableBoat, get_number_of_ways_with},
+                       sheet::{RaceSheet, parse_sheets},
+                       solver::get_number_of_ways_big};
+
+pub mod boat_race;
 
 #[derive(Debug)]
 enum TypeRun {
@@ -65,167 +73,168 @@ enum TypeRun {
 }
 
 struct BoatRaceDb {
-    times: Vec<u64>,
-    distances: Vec<u64>,
+    times: Vec<BigUint>,
+    distances: Vec<BigUint>,
 }
 
 impl BoatRaceDb {
 
-    fn new () -> Self {
+    fn from_sheet(sheet: &RaceSheet, type_run: &TypeRun) -> Self {
 
-        BoatRaceDb {
-            times: Vec::new(),
-            distances: Vec::new(),
-        }
-    }
+        let races = match type_run {
+            TypeRun::FirstPart => sheet.get_races(),
+            TypeRun::SecondPart => vec![sheet.get_joined_race()],
+        };
 
-    fn populate_times_like_vector(&mut self, times_info_str: &str) {
-        let data: Vec<&str> = times_info_str.split(':').collect();
-        match data[1].split_whitespace().map(|num| 
-            num.parse::<u64>()).collect::<Result<Vec<_>, _>>() { 
-                Ok(numbers) => self.times.extend(numbers) ,
-                Err(_) => println!("not able to transform string to times :("),
-            
-        }
+        let (times, distances) = races.into_iter().unzip();
+        BoatRaceDb { times, distances }
     }
 
-    fn populates_distances_like_vector(&mut self, distances_info_str: &str) {
-        let data: Vec<&str> = distances_info_str.split(':').collect();
-        match data[1].split_whitespace().map(|num| 
-            num.parse::<u64>()).collect::<Result<Vec<_>, _>>() { 
-                Ok(numbers) => self.distances.extend(numbers) ,
-                Err(_) => println!("not able to transform string to distances :("),
-            
-        }
-    }
+    // Races of any size with the puzzle boat, other boats need the time to fit in
+    // a u64 and the record in a u128
+    fn get_number_of_ways(&self, physics: Option<&dyn BoatPhysics>) -> std::io::Result<BigUint> {
 
-    fn populate_times_single_number(&mut self, times_info_str: &str){
-        let data: Vec<&str> = times_info_str.split(':').collect();        
-        self.times.push(Self::list_num_in_str_to_u64(data[1]));
-    }
-
-    fn populate_distances_single_number(&mut self, distances_info_str: &str) {
-        let data: Vec<&str> = distances_info_str.split(':').collect();
-        self.distances.push(Self::list_num_in_str_to_u64(data[1]));
-    }
-
-    fn list_num_in_str_to_u64(numbers_list_str: &str) -> u64 {
-        let numbers_str: Vec<&str> = numbers_list_str.split_whitespace().collect();
-
-        let mut single_num: u64 = 0;
-        for num in numbers_str {
+        let mut number_of_ways = BigUint::from_u64(1);
+        
+        for (i, time) in self.times.iter().enumerate() {
 
-            single_num = single_num *  10u64.pow(num.len().try_into().unwrap()) + num.parse::<u64>().unwrap();
+            let race_ways = match physics {
+                None => get_number_of_ways_big(time, &self.distances[i]),
+                Some(physics) => {
+                    let (Some(time), Some(distance)) = (time.to_u64(), self.distances[i].to_u128()) else {
+                        return Err(std::io::Error::other(format!("Race {} is too long for this boat :(", i + 1)));
+                    };
+                    BigUint::from_u128(get_number_of_ways_with(physics, time, distance))
+                },
+            };
+
+            number_of_ways = &number_of_ways * &race_ways;
         }
 
-        single_num
+        Ok(number_of_ways)
     }
-    
-    fn get_number_of_ways(&self) -> u64 {
+}
 
-        let mut number_of_ways: u64 = 1;
-        
-        for (i, time) in self.times.iter().enumerate() {
+fn main () -> std::io::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+
+    match args.get(1).map(|arg| arg.as_str()) {
+        // Best hold times, best distance, winning holds and margin of every race of
+        // Part One, or of Part Two with --second, and --plot to draw the small races
+        Some("report") => {
+            let type_run = if args.iter().any(|arg| arg == "--second") {TypeRun::SecondPart} else {TypeRun::FirstPart};
+            let plot = args.iter().any(|arg| arg == "--plot");
+            report(type_run, read_physics(&args)?.as_ref(), plot)?;
+        },
+        // Any of --acceleration, --max-speed, --drag or --charge-delay followed by
+        // a number races another boat, e.g. --acceleration 2 --drag 1
+        _ => {
+            let physics = read_physics(&args)?;
+            algorithm(TypeRun::FirstPart, physics.as_ref())?;
+            algorithm(TypeRun::SecondPart, physics.as_ref())?;
+        },
+    }
 
-            number_of_ways *= BoatRaceDb::get_margin(*time, self.distances[i]);
-        }
+    Ok(())
+}
 
-        number_of_ways
+fn read_physics(args: &[String]) -> std::io::Result<Option<ConfigurableBoat>> {
+
+    let mut boat = ConfigurableBoat { acceleration: 1, max_speed: None, drag: 0, charge_delay: 0 };
+    let mut any_flag = false;
+
+    for (i, arg) in args.iter().enumerate() {
+        let field = match arg.as_str() {
+            "--acceleration" => &mut boat.acceleration,
+            "--drag" => &mut boat.drag,
+            "--charge-delay" => &mut boat.charge_delay,
+            "--max-speed" => boat.max_speed.insert(0),
+            _ => continue,
+        };
+
+        let Some(value) = args.get(i + 1) else {
+            return Err(std::io::Error::other(format!("Expected a number after {arg} :(")));
+        };
+        *field = value.parse::<u64>().map_err(std::io::Error::other)?;
+        any_flag = true;
     }
 
-    fn get_margin(time: u64, distance_to_beat: u64) -> u64
-    {
-        let lower_limit_inclusive = BoatRaceDb::get_lower_limit(time, distance_to_beat);
-        let upper_limit_inclusive = BoatRaceDb::get_upper_limit(time,lower_limit_inclusive);
+    Ok(any_flag.then_some(boat))
+}
 
-        upper_limit_inclusive - lower_limit_inclusive 
-    }
+fn read_sheets() -> std::io::Result<Vec<RaceSheet>> {
 
-    fn get_lower_limit(time: u64, distance_to_beat: u64) -> u64 
-    {
-        BoatRaceDb::binary_search(0, time, distance_to_beat)
-    }
+    // Open the file for reading
+    let file: File = File::open("data/input.txt")?;
 
-    fn get_upper_limit(time: u64, lower_bound_time:u64) -> u64 
-    {
-        time - lower_bound_time + 1
-    }
+    // Create a buffered reader to read the file
+    let reader: BufReader<File> = BufReader::new(file);
 
-    fn binary_search(mut low_time: u64, mut high_time: u64, target_distance: u64) -> u64 {
+    parse_sheets(reader).map_err(|err| std::io::Error::other(format!("Could not read the race sheet :( {err}")))
+}
 
-        let mut mid_time: u64;
-        let mut distance_calculated: u64;
-        let max_time = high_time;
+fn report(type_run: TypeRun, physics: Option<&ConfigurableBoat>, plot: bool) -> std::io::Result<()> {
 
-        while low_time <= high_time{
-            mid_time = low_time + (high_time - low_time) / 2;
-            distance_calculated = BoatRaceDb::calculate_distance(mid_time, max_time);
+    let sheets = read_sheets()?;
+    let physics: &dyn BoatPhysics = match physics {
+        Some(boat) => boat,
+        None => &ClassicBoat,
+    };
 
-            // If x greater, ignore left half
-            if distance_calculated < target_distance {
-                low_time = mid_time + 1;
-            }
+    for (sheet_index, sheet) in sheets.iter().enumerate() {
+        let boat_race_db = BoatRaceDb::from_sheet(sheet, &type_run);
 
-            // If x is smaller, ignore right half
-            else{
-
-                if mid_time == 0 {
-                    return mid_time;
-                }
-                else if BoatRaceDb::calculate_distance(mid_time-1, max_time) <= target_distance{
-                    return mid_time;
-                }
-                else {
-                    high_time = mid_time - 1;
-                }
+        if sheets.len() > 1 {
+            println!("{}Sheet {}", if sheet_index > 0 {"\n"} else {""}, sheet_index + 1);
+        }
+        println!("{:>4} {:>8} {:>10} {:>17} {:>12} {:>17} {:>8} {:>10}",
+            "race", "time", "record", "best holds", "best", "winning holds", "ways", "margin");
+
+        for (i, (time, distance)) in boat_race_db.times.iter().zip(&boat_race_db.distances).enumerate() {
+            let (Some(time), Some(distance)) = (time.to_u64(), distance.to_u128()) else {
+                println!("{:>4} too long to report", i + 1);
+                continue;
+            };
+
+            let race_report = get_race_report(physics, time, distance);
+            let (first_best, last_best) = race_report.optimal_holds;
+            let winning_holds = match race_report.winning_interval {
+                Some((lower, upper)) => format!("{lower}-{upper}"),
+                None => String::from("-"),
+            };
+            let margin = race_report.margin.map_or(String::from("-"), |margin| margin.to_string());
+
+            println!("{:>4} {:>8} {:>10} {:>17} {:>12} {:>17} {:>8} {:>10}", i + 1, race_report.time,
+                race_report.distance_to_beat, format!("{first_best}-{last_best}"), race_report.max_distance,
+                winning_holds, race_report.number_of_ways, margin);
+
+            if plot && time <= MAX_PLOT_TIME {
+                println!("\n{}", race_to_plot(physics, time, distance, 60));
+            }
+            else if plot {
+                println!("\nRace {} is too long to plot, up to {MAX_PLOT_TIME} ms can be drawn\n", i + 1);
             }
         }
-
-        // If we reach here, then element was not present
-        0
     }
 
-    fn calculate_distance(time_pressed_button:u64, max_time:u64) -> u64{
-        (max_time - time_pressed_button) * time_pressed_button
-    }
-}
-
-fn main () -> std::io::Result<()> {
-    algorithm(TypeRun::FirstPart)?;
-    algorithm(TypeRun::SecondPart)?;
-
     Ok(())
 }
 
-fn algorithm(type_run: TypeRun) -> std::io::Result<()>{
-
-    // Open the file for reading
-    let file: File = File::open("data/input.txt")?;
-
-    // Create a buffered reader to read the file
-    let reader: BufReader<File> = BufReader::new(file);
-
-    let mut boat_race_db = BoatRaceDb::new();
+fn algorithm(type_run: TypeRun, physics: Option<&ConfigurableBoat>) -> std::io::Result<()>{
 
-    for line in reader.lines() {
+    let sheets = read_sheets()?;
 
-        let line_str = line?;
+    for (i, sheet) in sheets.iter().enumerate() {
+        let boat_race_db = BoatRaceDb::from_sheet(sheet, &type_run);
+        let number_of_ways = boat_race_db.get_number_of_ways(physics.map(|boat| boat as &dyn BoatPhysics))?;
 
-        if line_str.contains("Time:") {
-            match type_run {
-                TypeRun::FirstPart => boat_race_db.populate_times_like_vector(&line_str),
-                TypeRun::SecondPart => boat_race_db.populate_times_single_number(&line_str),
-            }
+        if sheets.len() > 1 {
+            println!("The result of {:?} of Wait for It for sheet {} is: {}", type_run, i + 1, number_of_ways);
         }
-        else if line_str.contains("Distance:") {
-            match type_run {
-                TypeRun::FirstPart => boat_race_db.populates_distances_like_vector(&line_str),
-                TypeRun::SecondPart => boat_race_db.populate_distances_single_number(&line_str),
-            }
+        else {
+            println!("The result of {:?} of Wait for It is: {}", type_run, number_of_ways);
         }
     }
 
-    println!("The result of {:?} of Wait for It is: {}", type_run, boat_race_db.get_number_of_ways());
-
     Ok(())
 }